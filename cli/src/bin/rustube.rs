@@ -108,7 +108,7 @@ struct DownloadArgs {
     #[clap(
     short, long,
     about = "\
-    The filename of the video file [default: <VIDEO_ID>.mp4]\n\
+    The filename of the video file [default: <VIDEO_ID>.f<ITAG>.<EXTENSION>]\n\
     If the file already exists, it will be removed, even if the download fails!\
     "
     )]
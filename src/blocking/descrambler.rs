@@ -1,7 +1,7 @@
 use std::ops::{Deref, DerefMut};
 
 use crate::blocking::video::Video;
-use crate::descrambler::VideoDescrambler as AsyncVideoDescrambler;
+use crate::descrambler::{Decipher, VideoDescrambler as AsyncVideoDescrambler};
 use crate::Result;
 
 /// A synchronous wrapper around [`VideoDescrambler`](crate::VideoDescrambler).
@@ -14,6 +14,12 @@ impl VideoDescrambler {
     pub fn descramble(self) -> Result<Video> {
         Ok(Video(self.0.descramble()?))
     }
+
+    /// A synchronous wrapper around [`VideoDescrambler::with_decipher`](crate::VideoDescrambler::with_decipher).
+    #[inline]
+    pub fn with_decipher(self, decipher: impl Decipher + 'static) -> Self {
+        Self(self.0.with_decipher(decipher))
+    }
 }
 
 impl Deref for VideoDescrambler {
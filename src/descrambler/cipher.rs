@@ -5,6 +5,8 @@ use regex::Regex;
 
 use crate::{Error, Result, TryCollect};
 
+use super::Decipher;
+
 pub(crate) type TransformerFn = (fn(&mut Vec<u8>, Option<isize>), &'static str);
 
 static JS_FUNCTION_REGEX: SyncLazy<Regex> = SyncLazy::new(||
@@ -132,6 +134,27 @@ impl Cipher {
     }
 }
 
+impl Decipher for Cipher {
+    #[inline]
+    fn decipher(&self, _js: &str, signature: &str) -> Result<String> {
+        let mut signature = signature.to_owned();
+        self.decrypt_signature(&mut signature)
+            .map_err(cipher_error)?;
+        Ok(signature)
+    }
+}
+
+/// Turns errors of the built-in [`Cipher`] into [`Error::Cipher`], so it's clear, that the cipher
+/// needs to be updated. [`Error::Fatal`] is passed through unchanged.
+#[inline]
+pub(crate) fn cipher_error(error: Error) -> Error {
+    match error {
+        Error::Fatal(_) | Error::Cipher(_) => error,
+        Error::UnexpectedResponse(msg) => Error::Cipher(msg),
+        error => Error::Cipher(error.to_string().into()),
+    }
+}
+
 fn get_transform_plan(js: &str) -> Result<Vec<String>> {
     let name = regex::escape(get_initial_function_name(js)?);
     let pattern = Regex::new(&format!(r#"{}=function\(\w\)\{{[a-z=.(")]*;(.*);(?:.+)}}"#, name)).unwrap();
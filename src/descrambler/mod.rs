@@ -7,7 +7,7 @@ use cipher::Cipher;
 
 use crate::{IdBuf, Stream, Video, VideoDetails, VideoInfo};
 use crate::error::Error;
-use crate::video_info::player_response::streaming_data::RawFormat;
+use crate::video_info::player_response::streaming_data::{RawFormat, SignatureCipher};
 use crate::video_info::player_response::streaming_data::StreamingData;
 
 mod cipher;
@@ -82,7 +82,34 @@ pub struct VideoDescrambler {
     pub(crate) video_info: VideoInfo,
     #[derivative(Debug = "ignore", PartialEq = "ignore")]
     pub(crate) client: Client,
+    #[derivative(Debug = "ignore", PartialEq = "ignore")]
+    pub(crate) client_config: Option<crate::fetcher::ClientConfig>,
     pub(crate) js: String,
+    #[derivative(Debug = "ignore", PartialEq = "ignore")]
+    pub(crate) decipher: Option<Arc<dyn Decipher>>,
+}
+
+/// A custom way of deciphering the signature of a
+/// [`SignatureCipher`](crate::video_info::player_response::streaming_data::SignatureCipher).
+///
+/// YouTube changes its signature cipher from time to time, which breaks the built-in deciphering
+/// until `rustube` is updated. A [`Decipher`] allows you to patch around such changes yourself,
+/// by passing it to [`VideoDescrambler::with_decipher`].
+///
+/// [`Decipher`] is implemented for all closures with a matching signature.
+pub trait Decipher: Send + Sync {
+    /// Deciphers a single `signature`.
+    /// `js` is the JavaScript of the video's player, which contains the cipher YouTube currently
+    /// uses.
+    fn decipher(&self, js: &str, signature: &str) -> crate::Result<String>;
+}
+
+impl<F> Decipher for F
+    where F: Fn(&str, &str) -> crate::Result<String> + Send + Sync {
+    #[inline]
+    fn decipher(&self, js: &str, signature: &str) -> crate::Result<String> {
+        self(js, signature)
+    }
 }
 
 impl VideoDescrambler {
@@ -104,12 +131,13 @@ impl VideoDescrambler {
             apply_descrambler_adaptive_fmts(streaming_data, adaptive_fmts_raw)?;
         }
 
-        apply_signature(streaming_data, &self.js)?;
+        apply_signature(streaming_data, &self.js, self.decipher.as_deref())?;
         let mut streams = Vec::new();
         Self::initialize_streams(
             streaming_data,
             &mut streams,
             &self.client,
+            &self.client_config,
             &self.video_info.player_response.video_details,
         );
 
@@ -119,6 +147,14 @@ impl VideoDescrambler {
         })
     }
 
+    /// Uses the provided [`Decipher`] to decipher the signatures of the video's [`Stream`]s in
+    /// [`VideoDescrambler::descramble`], instead of the built-in one.
+    #[inline]
+    pub fn with_decipher(mut self, decipher: impl Decipher + 'static) -> Self {
+        self.decipher = Some(Arc::new(decipher));
+        self
+    }
+
     /// The [`VideoInfo`] of the video.
     #[inline]
     pub fn video_info(&self) -> &VideoInfo {
@@ -149,6 +185,7 @@ impl VideoDescrambler {
         streaming_data: &mut StreamingData,
         streams: &mut Vec<Stream>,
         client: &Client,
+        client_config: &Option<crate::fetcher::ClientConfig>,
         video_details: &Arc<VideoDetails>,
     ) {
         for raw_format in streaming_data.formats.drain(..).chain(streaming_data.adaptive_formats.drain(..)) {
@@ -156,7 +193,7 @@ impl VideoDescrambler {
                 raw_format,
                 client.clone(),
                 Arc::clone(video_details),
-            );
+            ).with_client_config(client_config.clone());
             streams.push(stream);
         }
     }
@@ -182,13 +219,23 @@ fn apply_descrambler_adaptive_fmts(streaming_data: &mut StreamingData, adaptive_
 }
 
 /// Descrambles the signature of a video.
+/// Uses `decipher`, if provided, and the built-in [`Cipher`] otherwise.
 #[inline]
-fn apply_signature(streaming_data: &mut StreamingData, js: &str) -> crate::Result<()> {
-    let cipher = Cipher::from_js(js)?;
+fn apply_signature(streaming_data: &mut StreamingData, js: &str, decipher: Option<&dyn Decipher>) -> crate::Result<()> {
+    let built_in;
+    let decipher: &dyn Decipher = match decipher {
+        Some(decipher) => decipher,
+        None => {
+            built_in = Cipher::from_js(js).map_err(cipher::cipher_error)?;
+            &built_in
+        }
+    };
 
     for raw_format in streaming_data.formats.iter_mut().chain(streaming_data.adaptive_formats.iter_mut()) {
-        let url = &mut raw_format.signature_cipher.url;
-        let s = match raw_format.signature_cipher.s {
+        let signature_cipher = &mut raw_format.signature_cipher;
+        let sp = signature_cipher.sp.as_deref().unwrap_or(SignatureCipher::DEFAULT_SIGNATURE_PARAM);
+        let url = &mut signature_cipher.url;
+        let s = match signature_cipher.s {
             Some(ref mut s) => s,
             None if url_already_contains_signature(url) => continue,
             None => return Err(Error::UnexpectedResponse(
@@ -196,10 +243,10 @@ fn apply_signature(streaming_data: &mut StreamingData, js: &str) -> crate::Resul
             ))
         };
 
-        cipher.decrypt_signature(s)?;
+        *s = decipher.decipher(js, s)?;
         url
             .query_pairs_mut()
-            .append_pair("sig", s);
+            .append_pair(sp, s);
     }
 
     Ok(())
@@ -13,6 +13,71 @@ pub enum Error {
     #[doc(cfg(feature = "download"))]
     #[error("the video contains no streams")]
     NoStreams,
+    #[cfg(any(feature = "download", doc))]
+    #[doc(cfg(feature = "download"))]
+    #[error("the download did not finish in time")]
+    Timeout,
+    #[cfg(any(feature = "download", doc))]
+    #[doc(cfg(feature = "download"))]
+    #[error("the download was cancelled")]
+    Cancelled,
+    #[cfg(any(feature = "download", doc))]
+    #[doc(cfg(feature = "download"))]
+    #[error("the download did not finish before its deadline")]
+    DeadlineExceeded,
+    #[cfg(any(feature = "download", doc))]
+    #[doc(cfg(feature = "download"))]
+    #[error("the range {0:?} is empty, or exceeds the content length")]
+    InvalidRange(core::ops::Range<u64>),
+    #[cfg(any(feature = "download", doc))]
+    #[doc(cfg(feature = "download"))]
+    #[error("YouTube rate limited the download (retry after: {retry_after:?})")]
+    RateLimited { retry_after: Option<core::time::Duration> },
+    #[cfg(any(feature = "download", doc))]
+    #[doc(cfg(feature = "download"))]
+    #[error("the stream exceeds the maximum size of {max_size} bytes")]
+    TooLarge { max_size: u64 },
+    #[cfg(any(feature = "download", doc))]
+    #[doc(cfg(feature = "download"))]
+    #[error("the download exceeds the size limit of {limit} bytes")]
+    SizeLimitExceeded { limit: u64 },
+    #[cfg(any(feature = "download", doc))]
+    #[doc(cfg(feature = "download"))]
+    #[error("the file {0:?} already exists")]
+    FileExists(std::path::PathBuf),
+    #[cfg(any(feature = "download", doc))]
+    #[doc(cfg(feature = "download"))]
+    #[error("the stream is empty, although it was expected to contain data")]
+    EmptyStream,
+    #[cfg(any(feature = "download", doc))]
+    #[doc(cfg(feature = "download"))]
+    #[error("the download is incomplete: expected {expected} bytes, but only {actual} bytes were written")]
+    Incomplete { expected: u64, actual: u64 },
+    #[cfg(any(feature = "download", doc))]
+    #[doc(cfg(feature = "download"))]
+    #[error("the stream was served with the unexpected content encoding `{0}`")]
+    UnexpectedContentEncoding(String),
+    #[cfg(any(feature = "download", doc))]
+    #[doc(cfg(feature = "download"))]
+    #[error(
+        "too many redirects{}",
+        .0.as_ref().map(|url| format!(" while requesting {}", url)).unwrap_or_default()
+    )]
+    TooManyRedirects(Option<url::Url>),
+    #[cfg(any(feature = "download", doc))]
+    #[doc(cfg(feature = "download"))]
+    #[error(
+        "failed writing at byte {offset}{}: {source}",
+        .path.as_ref().map(|path| format!(" to {}", path.display())).unwrap_or_default()
+    )]
+    Write {
+        /// The number of bytes, that were already written successfully.
+        offset: u64,
+        /// The file, that was written to, if the download was written to a single file.
+        path: Option<std::path::PathBuf>,
+        #[source]
+        source: std::io::Error,
+    },
 
     #[error(transparent)]
     #[cfg(any(feature = "fetch", doc))]
@@ -35,6 +100,11 @@ pub enum Error {
     #[error(transparent)]
     UrlParseError(#[from] url::ParseError),
 
+    #[error("the signature of the stream could not be deciphered, YouTube may have changed its cipher: {0}")]
+    #[cfg(any(feature = "descramble", doc))]
+    #[doc(cfg(feature = "descramble"))]
+    Cipher(Cow<'static, str>),
+
     #[error("{0}")]
     Custom(Cow<'static, str>),
     #[error("a potentially dangerous error occurred: {0}")]
@@ -48,4 +118,12 @@ pub enum Error {
     #[cfg(any(feature = "callback", doc))]
     #[doc(cfg(feature = "callback"))]
     ChannelClosed,
+    #[error("no ffmpeg binary could be found")]
+    #[cfg(any(feature = "mux", doc))]
+    #[doc(cfg(feature = "mux"))]
+    FfmpegNotFound,
+    #[error("ffmpeg exited with {0}: {1}")]
+    #[cfg(any(feature = "mux", doc))]
+    #[doc(cfg(feature = "mux"))]
+    Ffmpeg(std::process::ExitStatus, String),
 }
@@ -70,6 +70,8 @@ pub struct VideoFetcher {
     watch_url: Url,
     #[derivative(Debug = "ignore", PartialEq = "ignore")]
     client: Client,
+    #[derivative(Debug = "ignore", PartialEq = "ignore")]
+    client_config: Option<ClientConfig>,
 }
 
 impl VideoFetcher {
@@ -91,17 +93,27 @@ impl VideoFetcher {
     /// When [`reqwest`] fails to initialize an new [`Client`].
     #[inline]
     pub fn from_id(video_id: IdBuf) -> crate::Result<Self> {
+        Self::from_id_with_pool_settings(video_id, PoolSettings::default())
+    }
+
+    /// Constructs a [`VideoFetcher`] from an `Id`, whose [`Client`] (which is also used by all
+    /// [`Stream`](crate::Stream)s of the video) pools its connections according to `settings`.
+    /// ### Errors
+    /// When [`reqwest`] fails to initialize an new [`Client`].
+    #[inline]
+    pub fn from_id_with_pool_settings(video_id: IdBuf, settings: PoolSettings) -> crate::Result<Self> {
         // maybe make these feature gated, to prevent overhead for users that
         //  don't have problems with youtube consent
-        let cookie_jar = recommended_cookies();
-        let headers = recommended_headers();
-
-        let client = Client::builder()
-            .default_headers(headers)
-            .cookie_provider(std::sync::Arc::new(cookie_jar))
-            .build()?;
+        let client_config = ClientConfig {
+            settings,
+            cookie_jar: std::sync::Arc::new(recommended_cookies()),
+        };
+        let client = client_config.build(reqwest::redirect::Policy::default())?;
 
-        Ok(Self::from_id_with_client(video_id, client))
+        Ok(Self {
+            client_config: Some(client_config),
+            ..Self::from_id_with_client(video_id, client)
+        })
     }
 
     /// Constructs a [`VideoFetcher`] from an [`Id`] and an existing [`Client`].
@@ -114,6 +126,7 @@ impl VideoFetcher {
             watch_url: video_id.watch_url(),
             video_id,
             client,
+            client_config: None,
         }
     }
 
@@ -166,7 +179,9 @@ impl VideoFetcher {
         Ok(VideoDescrambler {
             video_info,
             client: self.client,
+            client_config: self.client_config,
             js,
+            decipher: None,
         })
     }
 
@@ -499,6 +514,107 @@ fn is_json_object_end(curr_char: u8, skip: &mut bool, stack: &mut Vec<u8>) -> bo
     stack.is_empty()
 }
 
+/// The connection pool settings of the [`Client`] a [`VideoFetcher`] creates, which is also used
+/// to download the [`Stream`](crate::Stream)s of the video.
+///
+/// Downloading many small segments (i.e. of OTF streams), or many streams at once, profits from
+/// keeping more idle connections alive, so they can be reused, instead of opening a new
+/// connection for every request.
+///
+/// ```no_run
+///# use rustube::{Id, VideoFetcher};
+///# use rustube::fetcher::PoolSettings;
+///# use std::time::Duration;
+///# let id = Id::from_str("5jlI4uzZGjU").unwrap().into_owned();
+/// let settings = PoolSettings::new()
+///     .max_idle_per_host(32)
+///     .idle_timeout(Duration::from_secs(300));
+/// let fetcher = VideoFetcher::from_id_with_pool_settings(id, settings).unwrap();
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PoolSettings {
+    max_idle_per_host: usize,
+    idle_timeout: Option<std::time::Duration>,
+    tcp_keepalive: Option<std::time::Duration>,
+}
+
+impl Default for PoolSettings {
+    /// The defaults of [`reqwest`]: an unlimited number of idle connections per host, which are
+    /// closed after 90 seconds, and no TCP keep-alive.
+    #[inline]
+    fn default() -> Self {
+        Self {
+            max_idle_per_host: usize::MAX,
+            idle_timeout: Some(std::time::Duration::from_secs(90)),
+            tcp_keepalive: None,
+        }
+    }
+}
+
+impl PoolSettings {
+    /// Creates the default [`PoolSettings`].
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The maximum number of idle connections kept alive per host.
+    #[inline]
+    pub fn max_idle_per_host(mut self, max_idle_per_host: usize) -> Self {
+        self.max_idle_per_host = max_idle_per_host;
+        self
+    }
+
+    /// How long an idle connection is kept alive, before it's closed. `None` keeps idle
+    /// connections alive forever.
+    #[inline]
+    pub fn idle_timeout(mut self, idle_timeout: impl Into<Option<std::time::Duration>>) -> Self {
+        self.idle_timeout = idle_timeout.into();
+        self
+    }
+
+    /// The interval of TCP keep-alive probes. `None` disables TCP keep-alive.
+    #[inline]
+    pub fn tcp_keepalive(mut self, tcp_keepalive: impl Into<Option<std::time::Duration>>) -> Self {
+        self.tcp_keepalive = tcp_keepalive.into();
+        self
+    }
+
+    /// Applies the settings to a [`ClientBuilder`](reqwest::ClientBuilder), i.e. to construct a
+    /// custom [`Client`] for [`VideoFetcher::from_id_with_client`].
+    #[inline]
+    pub fn apply(&self, builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+        builder
+            .pool_max_idle_per_host(self.max_idle_per_host)
+            .pool_idle_timeout(self.idle_timeout)
+            .tcp_keepalive(self.tcp_keepalive)
+    }
+}
+
+/// What a [`Client`] created by a [`VideoFetcher`] is made of, so an equivalent [`Client`] with
+/// another redirect policy can be built for the [`Stream`](crate::Stream)s of the video.
+#[derive(Clone)]
+pub(crate) struct ClientConfig {
+    settings: PoolSettings,
+    cookie_jar: std::sync::Arc<reqwest::cookie::Jar>,
+}
+
+impl ClientConfig {
+    /// Builds a [`Client`] with the [`PoolSettings`], the cookie jar, and the
+    /// [`recommended_headers`], which follows redirects according to `redirect`.
+    #[inline]
+    pub(crate) fn build(&self, redirect: reqwest::redirect::Policy) -> crate::Result<Client> {
+        Ok(
+            self.settings
+                .apply(Client::builder())
+                .default_headers(recommended_headers())
+                .cookie_provider(std::sync::Arc::clone(&self.cookie_jar))
+                .redirect(redirect)
+                .build()?
+        )
+    }
+}
+
 pub fn recommended_cookies() -> reqwest::cookie::Jar {
     let cookie = "CONSENT=YES+; Path=/; Domain=youtube.com; Secure; Expires=Sun, 10 Jan 2038 07:59:59 GMT;";
     let url = "https://youtube.com".parse().unwrap();
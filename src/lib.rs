@@ -127,8 +127,8 @@ unreachable_pub
 //! 
 //! ## Different ways of downloading
 //! As you may already have noticed, all the above examples just call [`Stream::download`], and then
-//! get back a path to a video. This path will always point to `<VIDEO_ID>.mp4` in the current 
-//! working directory. But what if you want to have a little more control over where
+//! get back a path to a video. This path will always point to `<VIDEO_ID>.f<ITAG>.<EXTENSION>` in the
+//! current working directory, where the extension matches the [`Stream`]s mime type. But what if you want to have a little more control over where
 //! to download the video to?
 //! 
 //! [`Stream::download_to_dir`] and [`Stream::download_to`] have your back! Those methods allow you
@@ -154,13 +154,18 @@ unreachable_pub
 //! - `download`: \[default\] Enables all utilities required for downloading videos.
 //! - `regex`: \[default\] Enables [`Id::from_raw`], which extracts valid `Id`s from arbitrary video
 //!   identifiers like URLs.
-//! - `serde`: \[default\] Enables [`serde`] support for [`Id`] (Keep in mind, that this feature
-//!   does not enable the `regex` automatically).
+//! - `serde`: Enables [`StreamInfo`], a [`serde`] serializable snapshot of a [`Stream`]s metadata.
+//!   ([`serde`] support for [`Id`] is always available. Keep in mind, that this feature does not
+//!   enable the `regex` automatically).
 //! - `std`: \[default\] Enables `std` usage, which a lot of things depend on.
 //! - `fetch`: \[default\] Enables [`VideoFetcher`], which can be used to fetch video information.
 //! - `descramble`: \[default\] Enables [`VideoDescrambler`], which can decrypt video signatures and is
 //!   necessary to extract the individual streams.
 //! - `stream`: \[default\] Enables [`Stream`], a representation of a video stream that can be used to download this particular stream.
+//! - `mux`: Enables [`Stream::mux_with`], which combines a video-only and an audio-only [`Stream`]
+//!   into a single file, using an `ffmpeg` binary.
+//! - `indicatif`: Together with `callback`, enables [`Callback::progress_bar`], which displays the
+//!   download progress in an [`indicatif`](https://docs.rs/indicatif) progress bar.
 //! - `blocking`: Enables the [`blocking`] API, which internally creates a [`tokio`] runtime for you
 //!   , so you don't have to care about it yourself. (Keep in mind, that this feature does not enable
 //!   any of the other features above automatically)
@@ -176,12 +181,15 @@ extern crate alloc;
 #[cfg(any(feature = "tokio", doc))]
 #[doc(cfg(feature = "tokio"))]
 pub use tokio;
+#[cfg(any(feature = "download", doc))]
+#[doc(cfg(feature = "download"))]
+pub use tokio_util::sync::CancellationToken;
 pub use url;
 
 #[doc(inline)]
 #[cfg(any(feature = "descramble", doc))]
 #[doc(cfg(feature = "descramble"))]
-pub use crate::descrambler::VideoDescrambler;
+pub use crate::descrambler::{Decipher, VideoDescrambler};
 #[doc(inline)]
 #[cfg(any(feature = "std", doc))]
 #[doc(cfg(feature = "std"))]
@@ -198,11 +206,19 @@ pub use crate::id::{Id, IdBuf};
 pub use crate::id::{EMBED_URL_PATTERN, ID_PATTERN, ID_PATTERNS, SHARE_URL_PATTERN, WATCH_URL_PATTERN};
 #[cfg(any(feature = "callback", doc))]
 #[doc(cfg(feature = "callback"))]
-pub use crate::stream::callback::{Callback, CallbackArguments, OnCompleteType, OnProgressType};
+pub use crate::stream::callback::{Callback, CallbackArguments, OnCompleteType, OnErrorType, OnProgressType, OnStartType, ProgressEvent};
 #[doc(inline)]
 #[cfg(any(feature = "stream", doc))]
 #[doc(cfg(feature = "stream"))]
-pub use crate::stream::Stream;
+pub use crate::stream::{AudioInfo, DownloadObserver, ProxyPool, Stream, StreamKind};
+#[doc(inline)]
+#[cfg(any(feature = "download", doc))]
+#[doc(cfg(feature = "download"))]
+pub use crate::stream::{DownloadOptions, DownloadOutcome, HeadInfo, OverwritePolicy, PauseHandle, ProbeResult, DEFAULT_MAX_BYTES};
+#[doc(inline)]
+#[cfg(any(feature = "serde", doc))]
+#[doc(cfg(feature = "serde"))]
+pub use crate::stream::StreamInfo;
 #[doc(inline)]
 #[cfg(any(feature = "descramble", doc))]
 #[doc(cfg(feature = "descramble"))]
@@ -258,7 +274,7 @@ mod serde_impl;
 /// The absolute most straightforward way of downloading a YouTube video in high quality!
 /// 
 /// Takes an arbitrary video identifier, like any video URL, or the video id, and downloads
-/// the video to `<VIDEO_ID>.mp4` in the current working directory. 
+/// the video to `<VIDEO_ID>.f<ITAG>.<EXTENSION>` in the current working directory. 
 /// 
 /// For more control over the download process have a look at the [`crate`] level documentation,
 /// or at the [`Video`] struct. 
@@ -277,7 +293,7 @@ pub async fn download_best_quality(video_identifier: &str) -> Result<std::path::
 /// The absolute most straightforward way of downloading a YouTube video in low quality!
 /// 
 /// Takes an arbitrary video identifier, like any video URL, or the video id, and downloads
-/// the video to `<VIDEO_ID>.mp4` in the current working directory.
+/// the video to `<VIDEO_ID>.f<ITAG>.<EXTENSION>` in the current working directory.
 ///
 /// For more control over the download process have a look at the [`crate`] level documentation,
 /// or at the [`Video`] struct. 
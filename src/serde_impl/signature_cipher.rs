@@ -19,7 +19,7 @@ pub(crate) fn deserialize<'de, D>(deserializer: D) -> Result<SignatureCipher, D:
 
     let both: EitherUrlOrCipher = serde_with::serde::Deserialize::deserialize(deserializer)?;
     match (both.url, both.signature_cipher) {
-        (Some(url), None) => Ok(SignatureCipher { url, s: None }),
+        (Some(url), None) => Ok(SignatureCipher { url, s: None, sp: None }),
         (None, Some(s)) => Ok(s),
         (None, None) => Err(serde_with::serde::de::Error::missing_field("signatureCipher")),
         (Some(_), Some(_)) => Err(serde_with::serde::de::Error::duplicate_field("url")),
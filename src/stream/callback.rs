@@ -1,11 +1,17 @@
+use std::collections::VecDeque;
 use std::future::Future;
 use std::path::PathBuf;
 use std::pin::Pin;
 use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
 
 use futures::FutureExt;
 use tokio::sync::mpsc;
-use tokio::sync::{mpsc::{Receiver, Sender}, Mutex};
+use tokio::sync::mpsc::{Receiver, Sender};
+
+use crate::Error;
 
 // maybe:
 //  pub type OnProgress = Box<dyn Fn(&dyn Any, &[u8], u32)>;
@@ -15,25 +21,151 @@ use tokio::sync::{mpsc::{Receiver, Sender}, Mutex};
 #[derive(Clone, derivative::Derivative)]
 #[derivative(Debug)]
 pub struct CallbackArguments {
-    pub current_chunk: usize,
+    /// The number of bytes downloaded so far.
+    pub current_chunk: u64,
+    /// The total number of bytes, if known.
+    /// This is always `None` for OTF streams, since their size is unknown before downloading them.
+    pub total_bytes: Option<u64>,
+    /// The fraction of the download that's already done, in the range `0.0..=1.0`.
+    /// `None`, when `total_bytes` is unknown.
+    pub fraction: Option<f64>,
+    /// The current download speed in bytes per second, measured over the last second.
+    /// `None`, when there's not enough data to compute it yet.
+    pub instant_bps: Option<f64>,
+    /// The average download speed in bytes per second since the download started.
+    /// `None`, when there's not enough data to compute it yet.
+    pub average_bps: Option<f64>,
+    /// The estimated time until the download is done, based on `average_bps`.
+    /// `None`, when either `total_bytes` or `average_bps` is unknown.
+    pub eta: Option<Duration>,
+}
+
+impl CallbackArguments {
+    #[inline]
+    fn new(current_chunk: u64, total_bytes: Option<u64>, throughput: &Throughput) -> Self {
+        let (instant_bps, average_bps) = throughput.bps();
+        Self {
+            current_chunk,
+            total_bytes,
+            fraction: total_bytes
+                .filter(|&total| total != 0)
+                .map(|total| current_chunk as f64 / total as f64),
+            instant_bps,
+            average_bps,
+            eta: total_bytes
+                .zip(average_bps)
+                .filter(|&(_, bps)| bps > 0.)
+                .map(|(total, bps)| {
+                    let remaining = total.saturating_sub(current_chunk);
+                    Duration::from_secs_f64(remaining as f64 / bps)
+                }),
+        }
+    }
+}
+
+/// Keeps track of the download speed.
+struct Throughput {
+    start: Instant,
+    /// The progress updates of the last [`Throughput::WINDOW`], used for the instant speed.
+    samples: VecDeque<(Instant, u64)>,
+}
+
+impl Throughput {
+    const WINDOW: Duration = Duration::from_secs(1);
+
+    #[inline]
+    fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            samples: VecDeque::new(),
+        }
+    }
+
+    #[inline]
+    fn record(&mut self, bytes: u64) {
+        let now = Instant::now();
+        self.samples.push_back((now, bytes));
+        // always keep at least two samples, so there's something to compare to
+        while self.samples.len() > 2 && now.duration_since(self.samples[0].0) > Self::WINDOW {
+            self.samples.pop_front();
+        }
+    }
+
+    /// The instant and the average speed in bytes per second.
+    fn bps(&self) -> (Option<f64>, Option<f64>) {
+        let (latest, latest_bytes) = match self.samples.back() {
+            Some(&latest) => latest,
+            None => return (None, None),
+        };
+
+        let per_sec = |bytes: u64, since: Instant| {
+            let secs = latest.duration_since(since).as_secs_f64();
+            (secs > 0.).then(|| bytes as f64 / secs)
+        };
+
+        let (oldest, oldest_bytes) = self.samples[0];
+        (
+            per_sec(latest_bytes.saturating_sub(oldest_bytes), oldest),
+            per_sec(latest_bytes, self.start),
+        )
+    }
+}
+
+/// Merges the progress of multiple concurrently downloaded parts into a single total.
+///
+/// Every part reports the number of bytes it wrote so far, and only the growth since its last
+/// report is added to the total, so parts finishing out of order never make the total shrink.
+pub(crate) struct ProgressAggregator {
+    parts: Box<[AtomicU64]>,
+    total: AtomicU64,
+}
+
+impl ProgressAggregator {
+    #[inline]
+    pub(crate) fn new(parts: usize) -> Self {
+        Self {
+            parts: (0..parts).map(|_| AtomicU64::new(0)).collect(),
+            total: AtomicU64::new(0),
+        }
+    }
+
+    /// Records, that `part` wrote `bytes` bytes so far, and returns the new total.
+    #[inline]
+    pub(crate) fn update(&self, part: usize, bytes: u64) -> u64 {
+        let previous = self.parts[part].fetch_max(bytes, Ordering::Relaxed);
+        let added = bytes.saturating_sub(previous);
+        self.total.fetch_add(added, Ordering::Relaxed) + added
+    }
+}
+
+/// An event yielded by [`Stream::download_to_progress`](super::Stream::download_to_progress).
+#[doc(cfg(feature = "callback"))]
+#[derive(Debug)]
+pub enum ProgressEvent {
+    /// The download made progress.
+    Progress(CallbackArguments),
+    /// The download is done. This is always the last event.
+    /// Contains the number of bytes written to the file, or the error the download failed with.
+    Completed(crate::Result<u64>),
 }
 
 /// Type to process on_progress
 #[doc(cfg(feature = "callback"))]
+#[derive(Clone)]
 pub enum OnProgressType {
-    /// Box containing a closure to execute on progress
-    Closure(Box<dyn Fn(CallbackArguments)>),
-    /// Box containing a async closure to execute on progress
-    AsyncClosure(Box<dyn Fn(CallbackArguments) -> Pin<Box<dyn Future<Output = ()>>>>),
+    /// Arc containing a closure to execute on progress
+    Closure(Arc<dyn Fn(CallbackArguments) + Send + Sync>),
+    /// Arc containing a async closure to execute on progress
+    AsyncClosure(Arc<dyn Fn(CallbackArguments) -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync>),
     /// Channel to send a message to on progress,
     /// bool indicates whether or not to cancel on a closed channel
     Channel(Sender<CallbackArguments>, bool),
-    /// Box containing a closure to execute on progress
+    /// Arc containing a closure to execute on progress
     /// Will get executed for every MB downloaded
-    SlowClosure(Box<dyn Fn(CallbackArguments)>),
-    /// Box containing a async closure to execute on progress
+    SlowClosure(Arc<dyn Fn(CallbackArguments) + Send + Sync>),
+    /// Arc containing a async closure to execute on progress
     /// Will get executed for every MB downloaded
-    SlowAsyncClosure(Box<dyn Fn(CallbackArguments) -> Pin<Box<dyn Future<Output = ()>>>>),
+    SlowAsyncClosure(Arc<dyn Fn(CallbackArguments) -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync>),
     /// Channel to send a message to on progress,
     /// bool indicates whether or not to cancel on a closed channel
     /// Will get executed for every MB downloaded
@@ -56,6 +188,16 @@ impl fmt::Debug for OnProgressType {
     }
 }
 
+impl OnProgressType {
+    #[inline]
+    fn is_slow(&self) -> bool {
+        matches!(
+            self,
+            OnProgressType::SlowClosure(_) | OnProgressType::SlowAsyncClosure(_) | OnProgressType::SlowChannel(_, _)
+        )
+    }
+}
+
 #[doc(cfg(feature = "callback"))]
 impl Default for OnProgressType {
     fn default() -> Self {
@@ -63,14 +205,44 @@ impl Default for OnProgressType {
     }
 }
 
+/// Type to process on_start
+#[doc(cfg(feature = "callback"))]
+#[derive(Clone)]
+pub enum OnStartType {
+    /// Arc containing a closure to execute on start
+    Closure(Arc<dyn Fn(Option<u64>) + Send + Sync>),
+    /// Arc containing a async closure to execute on start
+    AsyncClosure(Arc<dyn Fn(Option<u64>) -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync>),
+    None,
+}
+
+impl fmt::Debug for OnStartType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            &OnStartType::AsyncClosure(_) => "AsyncClosure(async Fn)",
+            &OnStartType::Closure(_) => "Closure(Fn)",
+            &OnStartType::None => "None",
+        };
+        f.write_str(name)
+    }
+}
+
+#[doc(cfg(feature = "callback"))]
+impl Default for OnStartType {
+    fn default() -> Self {
+        OnStartType::None
+    }
+}
+
 /// Type to process on_progress
 #[doc(cfg(feature = "callback"))]
+#[derive(Clone)]
 pub enum OnCompleteType {
-    /// Box containing a closure to execute on complete
-    Closure(Box<dyn Fn(Option<PathBuf>)>),
+    /// Arc containing a closure to execute on complete
+    Closure(Arc<dyn Fn(Option<PathBuf>) + Send + Sync>),
     // fixme: Find a way to store async closures
-    /// Box containing a async closure to execute on complete
-    AsyncClosure(Box<dyn Fn(Option<PathBuf>) -> Pin<Box<dyn Future<Output = ()>>>>),
+    /// Arc containing a async closure to execute on complete
+    AsyncClosure(Arc<dyn Fn(Option<PathBuf>) -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync>),
     None,
 }
 
@@ -92,29 +264,142 @@ impl Default for OnCompleteType {
     }
 }
 
-/// Methods and streams to process either on_progress or on_complete
+/// Type to process on_error
+#[doc(cfg(feature = "callback"))]
+#[derive(Clone)]
+pub enum OnErrorType {
+    /// Arc containing a closure to execute on error
+    ///
+    /// Since the closure only receives a reference to the [`Error`], there's no async variant.
+    /// If you need to do async work, clone what you need out of the error and spawn a task.
+    Closure(Arc<dyn Fn(&Error) + Send + Sync>),
+    None,
+}
+
+impl fmt::Debug for OnErrorType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            &OnErrorType::Closure(_) => "Closure(Fn)",
+            &OnErrorType::None => "None",
+        };
+        f.write_str(name)
+    }
+}
+
+#[doc(cfg(feature = "callback"))]
+impl Default for OnErrorType {
+    fn default() -> Self {
+        OnErrorType::None
+    }
+}
+
+/// Methods and streams to process either on_start, on_progress, on_error or on_complete
+///
+/// Any number of on_progress handlers can be attached. All of them receive every progress update.
+///
+/// All closures have to be [`Send`] and [`Sync`], so downloads using a [`Callback`] can be run
+/// inside of [`tokio::spawn`]ed tasks. The closures themselves are executed in the same task as
+/// the download.
 #[doc(cfg(feature = "callback"))]
 #[derive(Debug)]
 pub struct Callback {
-    pub on_progress: OnProgressType,
+    pub on_start: OnStartType,
+    pub on_progress: Vec<OnProgressType>,
+    pub on_error: OnErrorType,
     pub on_complete: OnCompleteType,
-    pub(crate) internal_sender: Sender<usize>,
-    pub(crate) internal_receiver: Option<Receiver<usize>>,
+    pub(crate) internal_sender: Option<Sender<u64>>,
+    pub(crate) internal_receiver: Option<Receiver<u64>>,
+    pub(crate) progress_interval: Option<Duration>,
+    capacity: usize,
+}
+
+/// Clones all handlers of the [`Callback`], but creates a fresh internal progress channel (with
+/// the same capacity), so the clone can be used for another download, even if the original
+/// [`Callback`] was already used.
+///
+/// This way, the same [`Callback`] can serve as a template for a series of downloads.
+#[doc(cfg(feature = "callback"))]
+impl Clone for Callback {
+    fn clone(&self) -> Self {
+        Self {
+            on_start: self.on_start.clone(),
+            on_progress: self.on_progress.clone(),
+            on_error: self.on_error.clone(),
+            on_complete: self.on_complete.clone(),
+            progress_interval: self.progress_interval,
+            ..Self::with_capacity(self.capacity)
+        }
+    }
 }
 
 #[doc(cfg(feature = "callback"))]
 impl Callback {
+    /// The capacity of the internal progress channel of a [`Callback`] created with
+    /// [`Callback::new`].
+    pub const DEFAULT_CAPACITY: usize = 100;
+
     /// Create a new callback struct without actual callbacks
+    #[inline]
     pub fn new() -> Self {
-        let (tx, rx) = mpsc::channel(100);
+        Self::with_capacity(Self::DEFAULT_CAPACITY)
+    }
+
+    /// Create a new callback struct without actual callbacks, whose internal progress channel can
+    /// buffer up to `capacity` progress updates.
+    ///
+    /// To not slow down the download, progress updates are dropped, whenever this channel is
+    /// full. So a larger capacity means more fine-grained progress updates, when the on_progress
+    /// handlers cannot keep up with the download, at the cost of more memory. A smaller capacity
+    /// means less updates, but also less work for the on_progress handlers.
+    ///
+    /// The default capacity used by [`Callback::new`] is [`Callback::DEFAULT_CAPACITY`].
+    ///
+    /// ### Panics
+    /// When `capacity` is `0`.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let (tx, rx) = mpsc::channel(capacity);
         Callback {
-            on_progress: OnProgressType::None,
+            on_start: OnStartType::None,
+            on_progress: Vec::new(),
+            on_error: OnErrorType::None,
             on_complete: OnCompleteType::None,
-            internal_sender: tx,
-            internal_receiver: Some(rx)
+            internal_sender: Some(tx),
+            internal_receiver: Some(rx),
+            progress_interval: None,
+            capacity,
         }
     }
 
+    /// Calls the on_progress handlers at most once per `interval`, i.e. to limit how often an
+    /// expensive handler re-renders a UI. Updates, that arrive in between, are coalesced into
+    /// the next call, and the final progress is always reported.
+    #[doc(cfg(feature = "callback"))]
+    #[inline]
+    pub fn with_progress_interval(mut self, interval: Duration) -> Self {
+        self.progress_interval = Some(interval);
+        self
+    }
+
+    /// Attach a closure to be executed once, before the download starts.
+    /// The closure receives the total size of the download in bytes, or `None`, if the size
+    /// is unknown (i.e. for OTF streams).
+    #[doc(cfg(feature = "callback"))]
+    #[inline]
+    pub fn connect_on_start_closure(mut self, closure: impl Fn(Option<u64>) + Send + Sync + 'static) -> Self {
+        self.on_start = OnStartType::Closure(Arc::new(closure));
+        self
+    }
+
+    /// Attach a async closure to be executed once, before the download starts.
+    /// The closure receives the total size of the download in bytes, or `None`, if the size
+    /// is unknown (i.e. for OTF streams).
+    #[doc(cfg(feature = "callback"))]
+    #[inline]
+    pub fn connect_on_start_closure_async<Fut: Future<Output = ()> + Send + 'static, F: Fn(Option<u64>) -> Fut + Send + Sync + 'static>(mut self, closure: F) -> Self {
+        self.on_start = OnStartType::AsyncClosure(Arc::new(move |arg| closure(arg).boxed()));
+        self
+    }
+
     /// Attach a closure to be executed on progress
     ///
     /// ### Warning:
@@ -124,8 +409,8 @@ impl Callback {
     /// [Callback::connect_on_progress_closure_slow](crate::stream::callback::Callback::connect_on_progress_closure_slow)
     #[doc(cfg(feature = "callback"))]
     #[inline]
-    pub fn connect_on_progress_closure(mut self, closure: impl Fn(CallbackArguments) + 'static) -> Self {
-        self.on_progress = OnProgressType::Closure(Box::new(closure));
+    pub fn connect_on_progress_closure(mut self, closure: impl Fn(CallbackArguments) + Send + Sync + 'static) -> Self {
+        self.on_progress.push(OnProgressType::Closure(Arc::new(closure)));
         self
     }
 
@@ -133,8 +418,8 @@ impl Callback {
     /// more seldom, around once for every MB downloaded.
     #[doc(cfg(feature = "callback"))]
     #[inline]
-    pub fn connect_on_progress_closure_slow(mut self, closure: impl Fn(CallbackArguments) + 'static) -> Self {
-        self.on_progress = OnProgressType::SlowClosure(Box::new(closure));
+    pub fn connect_on_progress_closure_slow(mut self, closure: impl Fn(CallbackArguments) + Send + Sync + 'static) -> Self {
+        self.on_progress.push(OnProgressType::SlowClosure(Arc::new(closure)));
         self
     }
 
@@ -147,8 +432,8 @@ impl Callback {
     /// [Callback::connect_on_progress_closure_async_slow](crate::stream::callback::Callback::connect_on_progress_closure_async_slow)
     #[doc(cfg(feature = "callback"))]
     #[inline]
-    pub fn connect_on_progress_closure_async<Fut: Future<Output = ()> + Send + 'static, F: Fn(CallbackArguments) -> Fut + 'static>(mut self, closure: F) -> Self {
-        self.on_progress = OnProgressType::AsyncClosure(box move |arg| closure(arg).boxed());
+    pub fn connect_on_progress_closure_async<Fut: Future<Output = ()> + Send + 'static, F: Fn(CallbackArguments) -> Fut + Send + Sync + 'static>(mut self, closure: F) -> Self {
+        self.on_progress.push(OnProgressType::AsyncClosure(Arc::new(move |arg| closure(arg).boxed())));
         self
     }
 
@@ -156,8 +441,8 @@ impl Callback {
     /// more seldom, around once for every MB downloaded.
     #[doc(cfg(feature = "callback"))]
     #[inline]
-    pub fn connect_on_progress_closure_async_slow<Fut: Future<Output = ()> + Send + 'static, F: Fn(CallbackArguments) -> Fut + 'static + Sync + Send>(mut self, closure: F) -> Self {
-        self.on_progress = OnProgressType::SlowAsyncClosure(box move |arg| closure(arg).boxed());
+    pub fn connect_on_progress_closure_async_slow<Fut: Future<Output = ()> + Send + 'static, F: Fn(CallbackArguments) -> Fut + Send + Sync + 'static>(mut self, closure: F) -> Self {
+        self.on_progress.push(OnProgressType::SlowAsyncClosure(Arc::new(move |arg| closure(arg).boxed())));
         self
     }
 
@@ -174,7 +459,7 @@ impl Callback {
         sender: Sender<CallbackArguments>,
         cancel_on_close: bool
     ) -> Self {
-        self.on_progress = OnProgressType::Channel(sender, cancel_on_close);
+        self.on_progress.push(OnProgressType::Channel(sender, cancel_on_close));
         self
     }
 
@@ -189,97 +474,191 @@ impl Callback {
         sender: Sender<CallbackArguments>,
         cancel_on_close: bool
     ) -> Self {
-        self.on_progress = OnProgressType::SlowChannel(sender, cancel_on_close);
+        self.on_progress.push(OnProgressType::SlowChannel(sender, cancel_on_close));
+        self
+    }
+
+    /// Attach a closure to be executed, when the download fails.
+    /// The closure receives the [`Error`] the download failed with, and is executed right before
+    /// the on_complete callback (which receives `None` in this case).
+    #[doc(cfg(feature = "callback"))]
+    #[inline]
+    pub fn connect_on_error_closure(mut self, closure: impl Fn(&Error) + Send + Sync + 'static) -> Self {
+        self.on_error = OnErrorType::Closure(Arc::new(closure));
         self
     }
 
     /// Attach a closure to be executed on complete
     #[doc(cfg(feature = "callback"))]
     #[inline]
-    pub fn connect_on_complete_closure(mut self, closure: impl Fn(Option<PathBuf>) + 'static) -> Self {
-        self.on_complete = OnCompleteType::Closure(Box::new(closure));
+    pub fn connect_on_complete_closure(mut self, closure: impl Fn(Option<PathBuf>) + Send + Sync + 'static) -> Self {
+        self.on_complete = OnCompleteType::Closure(Arc::new(closure));
         self
     }
 
     /// Attach a async closure to be executed on complete
     #[doc(cfg(feature = "callback"))]
     #[inline]
-    pub fn connect_on_complete_closure_async<Fut: Future<Output = ()> + Send + 'static, F: Fn(Option<PathBuf>) -> Fut + 'static>(mut self, closure: F) -> Self {
-        self.on_complete = OnCompleteType::AsyncClosure(box move |arg| closure(arg).boxed());
+    pub fn connect_on_complete_closure_async<Fut: Future<Output = ()> + Send + 'static, F: Fn(Option<PathBuf>) -> Fut + Send + Sync + 'static>(mut self, closure: F) -> Self {
+        self.on_complete = OnCompleteType::AsyncClosure(Arc::new(move |arg| closure(arg).boxed()));
+        self
+    }
+
+    /// Create a new callback struct, that displays the download progress in a terminal
+    /// progress bar.
+    ///
+    /// See [`Callback::connect_progress_bar`] for details.
+    #[cfg(any(feature = "indicatif", doc))]
+    #[doc(cfg(all(feature = "callback", feature = "indicatif")))]
+    pub fn progress_bar() -> Self {
+        let style = indicatif::ProgressStyle::default_bar()
+            .template("{bar:40.cyan/blue} {bytes}/{total_bytes} ({bytes_per_sec}, {eta})")
+            .expect("the progress bar template is valid")
+            .progress_chars("#>-");
+        let progress_bar = indicatif::ProgressBar::new(0).with_style(style);
+        Self::new().connect_progress_bar(progress_bar)
+    }
+
+    /// Wire the provided [`ProgressBar`](indicatif::ProgressBar) to the download.
+    ///
+    /// The length of the bar is set to the size of the download, once it's known, and its
+    /// position is updated on progress. When the download finishes, the bar is finished, and when
+    /// it fails, the bar is abandoned.
+    ///
+    /// ### Note:
+    /// This replaces the on_start, on_error, and on_complete handlers.
+    #[cfg(any(feature = "indicatif", doc))]
+    #[doc(cfg(all(feature = "callback", feature = "indicatif")))]
+    pub fn connect_progress_bar(self, progress_bar: indicatif::ProgressBar) -> Self {
+        let on_start = progress_bar.clone();
+        let on_progress = progress_bar.clone();
+        let on_error = progress_bar.clone();
+
         self
+            .connect_on_start_closure(move |total_bytes| {
+                if let Some(total_bytes) = total_bytes {
+                    on_start.set_length(total_bytes);
+                }
+            })
+            .connect_on_progress_closure(move |args| on_progress.set_position(args.current_chunk))
+            .connect_on_error_closure(move |_| on_error.abandon())
+            .connect_on_complete_closure(move |path| {
+                if path.is_some() {
+                    progress_bar.finish();
+                }
+            })
     }
 }
 
 impl super::Stream {
     #[inline]
-    pub(crate) async fn on_progress(mut receiver: Receiver<usize>, on_progress: OnProgressType) {
-        let counter = Mutex::new(100);
-        match on_progress {
-            OnProgressType::None => {},
-            OnProgressType::Closure(closure) => {
-                while let Some(data) = receiver.recv().await {
-                    let arguments = CallbackArguments { current_chunk: data };
-                    closure(arguments);
-                }
-            }
-            OnProgressType::AsyncClosure(closure) => {
-                while let Some(data) = receiver.recv().await {
-                    let arguments = CallbackArguments { current_chunk: data };
-                    closure(arguments).await;
-                }
-            }
-            OnProgressType::Channel(sender, cancel_on_close) => {
-                while let Some(data) = receiver.recv().await {
-                    let arguments = CallbackArguments { current_chunk: data };
-                    // await if channel is full
-                    match sender.send(arguments).await {
-                        // close channel to internal loop on closed outer channel
-                        Err(_) => if cancel_on_close {receiver.close()}
-                        _ => {}
+    pub(crate) async fn on_progress(
+        mut receiver: Receiver<u64>,
+        on_progress: Vec<OnProgressType>,
+        total_bytes: Option<u64>,
+        interval: Option<Duration>,
+    ) {
+        // every handler gets its own counter, so slow handlers are executed around once per MB,
+        // and remembers whether or not it has seen the latest update
+        let mut handlers: Vec<(OnProgressType, usize, bool)> = on_progress
+            .into_iter()
+            .map(|on_progress| (on_progress, 100, false))
+            .collect();
+        let mut last = None;
+        let mut last_call: Option<Instant> = None;
+        let mut throughput = Throughput::new();
+
+        while let Some(data) = receiver.recv().await {
+            // the final count is sent twice, if the last regular update wasn't dropped, and
+            // concurrently downloaded parts may send their totals out of order
+            if last.map_or(false, |last| data <= last) { continue; }
+            last = Some(data);
+
+            throughput.record(data);
+            if let Some(interval) = interval {
+                if last_call.map_or(false, |last_call| last_call.elapsed() < interval) {
+                    for (_, _, up_to_date) in handlers.iter_mut() {
+                        *up_to_date = false;
                     }
+                    continue;
                 }
+                last_call = Some(Instant::now());
             }
-            OnProgressType::SlowClosure(closure) => {
-                while let Some(data) = receiver.recv().await {
-                    if let Ok(mut counter) = counter.try_lock() {
-                        *counter += 1;
-                        if *counter > 100 {
-                            *counter = 0;
-                            let arguments = CallbackArguments { current_chunk: data };
-                            closure(arguments)
-                        }
+
+            let arguments = CallbackArguments::new(data, total_bytes, &throughput);
+            for (on_progress, counter, up_to_date) in handlers.iter_mut() {
+                if on_progress.is_slow() {
+                    *counter += 1;
+                    if *counter <= 100 {
+                        *up_to_date = false;
+                        continue;
                     }
+                    *counter = 0;
                 }
-            }
-            OnProgressType::SlowAsyncClosure(closure) => {
-                while let Some(data) = receiver.recv().await {
-                    if let Ok(mut counter) = counter.try_lock() {
-                        *counter += 1;
-                        if *counter > 100 {
-                            *counter = 0;
-                            let arguments = CallbackArguments { current_chunk: data };
-                            closure(arguments).await
-                        }
-                    }
+
+                *up_to_date = true;
+                if !Self::call_on_progress(on_progress, arguments.clone()).await {
+                    // close channel to internal loop on closed outer channel
+                    receiver.close()
                 }
             }
+        }
+
+        // Slow or throttled handlers may have skipped the last update, but should still see the
+        // final progress, so i.e. progress bars always reach 100%.
+        if let Some(data) = last {
+            let arguments = CallbackArguments::new(data, total_bytes, &throughput);
+            for (on_progress, _, _) in handlers.iter().filter(|(_, _, up_to_date)| !up_to_date) {
+                Self::call_on_progress(on_progress, arguments.clone()).await;
+            }
+        }
+    }
+
+    /// Hands the `arguments` to a single on_progress handler.
+    /// Returns `false`, if the download should be cancelled, because the handler's channel was
+    /// closed.
+    #[inline]
+    async fn call_on_progress(on_progress: &OnProgressType, arguments: CallbackArguments) -> bool {
+        match on_progress {
+            OnProgressType::None => {},
+            OnProgressType::Closure(closure) | OnProgressType::SlowClosure(closure) => {
+                closure(arguments)
+            }
+            OnProgressType::AsyncClosure(closure) | OnProgressType::SlowAsyncClosure(closure) => {
+                closure(arguments).await
+            }
+            OnProgressType::Channel(sender, cancel_on_close) |
             OnProgressType::SlowChannel(sender, cancel_on_close) => {
-                while let Some(data) = receiver.recv().await {
-                    if let Ok(mut counter) = counter.try_lock() {
-                        *counter += 1;
-                        if *counter > 100 {
-                            *counter = 0;
-                            let arguments = CallbackArguments { current_chunk: data };
-                            match sender.send(arguments).await {
-                                // close channel to internal loop on closed outer channel
-                                Err(_) => if cancel_on_close {receiver.close()}
-                                _ => {}
-                            }
-                        }
-                    }
+                // await if channel is full
+                if sender.send(arguments).await.is_err() {
+                    return !cancel_on_close;
                 }
             }
         }
+        true
+    }
+
+    #[inline]
+    pub(crate) async fn on_start(on_start: OnStartType, total_bytes: Option<u64>) {
+        match on_start {
+            OnStartType::None => {},
+            OnStartType::Closure(closure) => {
+                closure(total_bytes)
+            }
+            OnStartType::AsyncClosure(closure) => {
+                closure(total_bytes).await
+            }
+        }
+    }
+
+    #[inline]
+    pub(crate) fn on_error(on_error: OnErrorType, error: &Error) {
+        match on_error {
+            OnErrorType::None => {},
+            OnErrorType::Closure(closure) => {
+                closure(error)
+            }
+        }
     }
 
     #[inline]
@@ -0,0 +1,351 @@
+use std::ops::Range;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use reqwest::Client;
+use reqwest::header::HeaderMap;
+use tokio_util::sync::CancellationToken;
+
+use super::{Callback, DEFAULT_WRITE_BUFFER_SIZE};
+
+/// Options for downloading a [`Stream`](super::Stream), consumed by
+/// [`Stream::download_with`](super::Stream::download_with).
+///
+/// All options are optional. Without any options set, [`Stream::download_with`](super::Stream::download_with)
+/// behaves like [`Stream::download`](super::Stream::download).
+///
+/// ```no_run
+///# use rustube::{DownloadOptions, Stream};
+///# use std::time::Duration;
+///# async fn run(stream: Stream) -> rustube::Result<()> {
+/// let options = DownloadOptions::new()
+///     .to_dir("downloads")
+///     .timeout(Duration::from_secs(600))
+///     .retries(3)
+///     .throttle(1024 * 1024);
+/// let path = stream.download_with(options).await?;
+///# Ok(())
+///# }
+/// ```
+#[derive(Clone, derivative::Derivative)]
+#[derivative(Debug)]
+pub struct DownloadOptions {
+    pub(super) destination: Destination,
+    pub(super) callback: Option<Callback>,
+    pub(super) timeout: Option<Duration>,
+    pub(super) deadline: Option<Instant>,
+    pub(super) cancel: Option<CancellationToken>,
+    pub(super) retries: usize,
+    pub(super) retry_delay: Duration,
+    pub(super) integrity_retries: usize,
+    pub(super) range: Option<Range<u64>>,
+    pub(super) bytes_per_sec: Option<u64>,
+    pub(super) max_bytes: Option<u64>,
+    #[derivative(Debug = "ignore")]
+    pub(super) client: Option<Client>,
+    pub(super) max_redirects: Option<usize>,
+    pub(super) headers: Option<HeaderMap>,
+    pub(super) write_buffer_size: usize,
+    pub(super) read_buffer_size: Option<usize>,
+    pub(super) sequenced_window: Option<usize>,
+    pub(super) temp_dir: Option<PathBuf>,
+    pub(super) unique_file_name: bool,
+    pub(super) content_disposition: bool,
+    pub(super) overwrite: OverwritePolicy,
+    #[cfg(feature = "serde")]
+    pub(super) sidecar: bool,
+}
+
+/// Where a download is written to.
+#[derive(Clone, Debug)]
+pub(super) enum Destination {
+    /// `<video_id>.f<itag>.<extension>` in the current working directory.
+    Default,
+    /// `<video_id>.f<itag>.<extension>` in the directory.
+    Dir(PathBuf),
+    Path(PathBuf),
+}
+
+/// What [`Stream::download_with`](super::Stream::download_with) does, if the file it downloads
+/// to already exists.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum OverwritePolicy {
+    /// The existing file is truncated, and the [`Stream`](super::Stream) is downloaded again.
+    Overwrite,
+    /// The download is skipped, and the path of the existing file is returned.
+    Skip,
+    /// The download fails with [`Error::FileExists`](crate::Error::FileExists).
+    Error,
+    /// The existing file is treated as a partial download, which is continued.
+    ///
+    /// OTF streams are resumed from the last completely written segment, using
+    /// [`Stream::download_seq_resumable_to`](super::Stream::download_seq_resumable_to). All other
+    /// streams are resumed after the last written byte, using a `Range` request. If the file is
+    /// already complete, nothing is downloaded.
+    ///
+    /// If there's no file yet, the download starts from scratch, but is written directly to the
+    /// path, instead of a temporary `.part` file. Unlike other downloads, a failed resumed
+    /// download keeps its file, so it can be resumed again, using the same policy.
+    Resume,
+}
+
+impl Default for DownloadOptions {
+    fn default() -> Self {
+        Self {
+            destination: Destination::Default,
+            callback: None,
+            timeout: None,
+            deadline: None,
+            cancel: None,
+            retries: 0,
+            retry_delay: Self::DEFAULT_RETRY_DELAY,
+            integrity_retries: Self::DEFAULT_INTEGRITY_RETRIES,
+            range: None,
+            bytes_per_sec: None,
+            max_bytes: None,
+            client: None,
+            max_redirects: None,
+            headers: None,
+            write_buffer_size: DEFAULT_WRITE_BUFFER_SIZE,
+            read_buffer_size: None,
+            sequenced_window: None,
+            temp_dir: None,
+            unique_file_name: false,
+            content_disposition: false,
+            overwrite: OverwritePolicy::Overwrite,
+            #[cfg(feature = "serde")]
+            sidecar: false,
+        }
+    }
+}
+
+impl DownloadOptions {
+    /// The time to wait between two attempts, if not specified otherwise.
+    pub const DEFAULT_RETRY_DELAY: Duration = Duration::from_secs(1);
+    /// The number of times an incomplete download is redownloaded, if not specified otherwise.
+    pub const DEFAULT_INTEGRITY_RETRIES: usize = 1;
+
+    /// Creates [`DownloadOptions`], which download to `<video_id>.f<itag>.<extension>` in the current
+    /// working directory.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Downloads to the provided file path.
+    #[inline]
+    pub fn to_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.destination = Destination::Path(path.into());
+        self
+    }
+
+    /// Downloads to `<video_id>.f<itag>.<extension>` in the provided directory.
+    #[inline]
+    pub fn to_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.destination = Destination::Dir(dir.into());
+        self
+    }
+
+    /// Adds the process id and the current time to the default file name
+    /// (`<video_id>.f<itag>.<pid>-<nanos>.<extension>`), so concurrent downloads of the same
+    /// [`Stream`](super::Stream) to the same directory never collide.
+    /// Has no effect, when downloading to a path.
+    #[inline]
+    pub fn unique_file_name(mut self, unique: bool) -> Self {
+        self.unique_file_name = unique;
+        self
+    }
+
+    /// Names the file after the file name the server suggests using `Content-Disposition`, if
+    /// any, instead of the default file name. This costs an additional `HEAD` request.
+    /// Has no effect, when downloading to a path.
+    #[inline]
+    pub fn content_disposition(mut self, content_disposition: bool) -> Self {
+        self.content_disposition = content_disposition;
+        self
+    }
+
+    /// What to do, if the file already exists (default: [`OverwritePolicy::Overwrite`]).
+    #[inline]
+    pub fn overwrite(mut self, overwrite: OverwritePolicy) -> Self {
+        self.overwrite = overwrite;
+        self
+    }
+
+    /// Writes a JSON sidecar file with the metadata of the [`Stream`](super::Stream) next to the
+    /// downloaded file, once the download succeeded
+    /// (see [`Stream::write_sidecar`](super::Stream::write_sidecar)).
+    #[cfg(any(feature = "serde", doc))]
+    #[doc(cfg(feature = "serde"))]
+    #[inline]
+    pub fn sidecar(mut self, sidecar: bool) -> Self {
+        self.sidecar = sidecar;
+        self
+    }
+
+    /// Reports the progress of the download to the [`Callback`](crate::Callback).
+    /// When the download is retried, every attempt uses a clone of the [`Callback`](crate::Callback).
+    #[cfg(any(feature = "callback", doc))]
+    #[doc(cfg(feature = "callback"))]
+    #[inline]
+    pub fn callback(mut self, callback: Callback) -> Self {
+        self.callback = Some(callback);
+        self
+    }
+
+    /// Aborts every attempt, that doesn't finish within `timeout`, with
+    /// [`Error::Timeout`](crate::Error::Timeout).
+    #[inline]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Aborts the download with [`Error::DeadlineExceeded`](crate::Error::DeadlineExceeded), if
+    /// it doesn't finish before `deadline`. Unlike [`timeout`](DownloadOptions::timeout), the
+    /// deadline applies to the download as a whole, so retries are only started, if they can
+    /// start before the deadline.
+    ///
+    /// ### Note:
+    /// Like the [`timeout`](DownloadOptions::timeout), the deadline doesn't apply to
+    /// [`range`](DownloadOptions::range) downloads, and resumed downloads.
+    #[inline]
+    pub fn deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Aborts the download with [`Error::Cancelled`](crate::Error::Cancelled), once the `token`
+    /// gets cancelled.
+    #[inline]
+    pub fn cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancel = Some(token);
+        self
+    }
+
+    /// Retries the download up to `retries` times, if it fails with a transient error, i.e. a
+    /// connection error, a server error, or when YouTube rate limits the download.
+    #[inline]
+    pub fn retries(mut self, retries: usize) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// The time to wait between two attempts (default: [`DownloadOptions::DEFAULT_RETRY_DELAY`]).
+    /// When YouTube rate limits the download, and tells how long to wait, that time is used
+    /// instead.
+    #[inline]
+    pub fn retry_delay(mut self, retry_delay: Duration) -> Self {
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    /// Redownloads the whole [`Stream`](super::Stream) up to `integrity_retries` times
+    /// (default: [`DownloadOptions::DEFAULT_INTEGRITY_RETRIES`]), if the number of written bytes
+    /// differs from its content length, before failing with
+    /// [`Error::Incomplete`](crate::Error::Incomplete).
+    /// These attempts don't count towards [`retries`](DownloadOptions::retries), and are started
+    /// without waiting for the [`retry_delay`](DownloadOptions::retry_delay).
+    #[inline]
+    pub fn integrity_retries(mut self, integrity_retries: usize) -> Self {
+        self.integrity_retries = integrity_retries;
+        self
+    }
+
+    /// Only downloads the provided byte `range`, using a single `Range` request.
+    #[inline]
+    pub fn range(mut self, range: Range<u64>) -> Self {
+        self.range = Some(range);
+        self
+    }
+
+    /// Limits the download to `bytes_per_sec` bytes per second.
+    #[inline]
+    pub fn throttle(mut self, bytes_per_sec: u64) -> Self {
+        self.bytes_per_sec = Some(bytes_per_sec);
+        self
+    }
+
+    /// Aborts the download with [`Error::SizeLimitExceeded`](crate::Error::SizeLimitExceeded), once
+    /// the file would exceed `max_bytes` bytes, and removes the partially downloaded file.
+    /// If the content length is already known, and exceeds `max_bytes`, the download isn't even
+    /// started.
+    ///
+    /// The only exception are [resumed](OverwritePolicy::Resume) downloads: their file is kept,
+    /// since it already existed before the download, and the limit applies to the whole file.
+    #[inline]
+    pub fn max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Sends all requests using the provided [`Client`], instead of the one the
+    /// [`Stream`](super::Stream) was fetched with.
+    #[inline]
+    pub fn client(mut self, client: Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Follows at most `max_redirects` redirects, before failing with
+    /// [`Error::TooManyRedirects`](crate::Error::TooManyRedirects). Since media URLs aren't
+    /// expected to redirect, `0` turns every redirect into an error.
+    ///
+    /// The redirect policy is part of a [`Client`], so the [`Client`] the
+    /// [`Stream`](super::Stream) was fetched with is rebuilt with the same connection pool
+    /// settings and cookie jar. The download fails, if the options also contain a
+    /// [`client`](DownloadOptions::client), or if the [`Stream`](super::Stream) uses a
+    /// [`ProxyPool`](super::ProxyPool), or a [`Client`], that wasn't created by a
+    /// [`VideoFetcher`](crate::VideoFetcher), since the redirect policy of those has to be
+    /// configured when building them.
+    #[inline]
+    pub fn max_redirects(mut self, max_redirects: usize) -> Self {
+        self.max_redirects = Some(max_redirects);
+        self
+    }
+
+    /// Sends all requests with the provided additional headers
+    /// (see [`Stream::with_headers`](super::Stream::with_headers)).
+    #[inline]
+    pub fn headers(mut self, headers: HeaderMap) -> Self {
+        self.headers = Some(headers);
+        self
+    }
+
+    /// The size of the buffer received chunks are collected in, before writing them to the file
+    /// (see [`Stream::download_to_buffered`](super::Stream::download_to_buffered)).
+    #[inline]
+    pub fn write_buffer_size(mut self, write_buffer_size: usize) -> Self {
+        self.write_buffer_size = write_buffer_size;
+        self
+    }
+
+    /// Collects received chunks, until at least `read_buffer_size` bytes are available, before
+    /// writing them (see [`Stream::with_read_buffer_size`](super::Stream::with_read_buffer_size)).
+    #[inline]
+    pub fn read_buffer_size(mut self, read_buffer_size: usize) -> Self {
+        self.read_buffer_size = Some(read_buffer_size);
+        self
+    }
+
+    /// The maximum number of segments of a sequenced download, that are requested concurrently,
+    /// while still being written in order
+    /// (see [`Stream::with_sequenced_window`](super::Stream::with_sequenced_window)).
+    #[inline]
+    pub fn sequenced_window(mut self, sequenced_window: usize) -> Self {
+        self.sequenced_window = Some(sequenced_window);
+        self
+    }
+
+    /// Writes the temporary `<file name>.<pid>.part` file to the provided directory, instead of
+    /// next to the downloaded file, i.e. to download to a fast local disk, before moving the file
+    /// to a slow network mount.
+    ///
+    /// If the directory is on another file system than the destination, the finished file is
+    /// copied to the destination, instead of renaming it.
+    #[inline]
+    pub fn temp_dir(mut self, temp_dir: impl Into<PathBuf>) -> Self {
+        self.temp_dir = Some(temp_dir.into());
+        self
+    }
+}
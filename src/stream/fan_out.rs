@@ -0,0 +1,81 @@
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tokio::io::AsyncWrite;
+
+/// An [`AsyncWrite`], that writes everything to all of its writers.
+///
+/// Writes are only reported as done, once every writer has written the whole buffer. Until then,
+/// the same buffer has to be passed to [`AsyncWrite::poll_write`] again, as i.e.
+/// [`AsyncWriteExt::write_all`](tokio::io::AsyncWriteExt::write_all) does.
+pub(super) struct FanOut<W> {
+    writers: Vec<W>,
+    /// How much of the current buffer every writer has written already.
+    written: Vec<usize>,
+}
+
+impl<W> FanOut<W> {
+    #[inline]
+    pub(super) fn new(writers: Vec<W>) -> Self {
+        Self {
+            written: vec![0; writers.len()],
+            writers,
+        }
+    }
+}
+
+impl<W: AsyncWrite + Unpin> AsyncWrite for FanOut<W> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        let mut pending = false;
+
+        // all writers are polled in every round, so they make progress concurrently
+        for (writer, written) in this.writers.iter_mut().zip(this.written.iter_mut()) {
+            while *written < buf.len() {
+                match Pin::new(&mut *writer).poll_write(cx, &buf[*written..]) {
+                    Poll::Ready(Ok(0)) => return Poll::Ready(Err(io::ErrorKind::WriteZero.into())),
+                    Poll::Ready(Ok(n)) => *written += n,
+                    Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                    Poll::Pending => {
+                        pending = true;
+                        break;
+                    }
+                }
+            }
+        }
+
+        if pending {
+            return Poll::Pending;
+        }
+
+        this.written.iter_mut().for_each(|written| *written = 0);
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let mut pending = false;
+        for writer in self.get_mut().writers.iter_mut() {
+            match Pin::new(writer).poll_flush(cx) {
+                Poll::Ready(Ok(())) => {}
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => pending = true,
+            }
+        }
+
+        if pending { Poll::Pending } else { Poll::Ready(Ok(())) }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let mut pending = false;
+        for writer in self.get_mut().writers.iter_mut() {
+            match Pin::new(writer).poll_shutdown(cx) {
+                Poll::Ready(Ok(())) => {}
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => pending = true,
+            }
+        }
+
+        if pending { Poll::Pending } else { Poll::Ready(Ok(())) }
+    }
+}
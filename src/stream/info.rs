@@ -0,0 +1,183 @@
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use mime::Mime;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_with::{DisplayFromStr, serde_as};
+use tokio::sync::OnceCell;
+
+use crate::video_info::player_response::streaming_data::{AudioQuality, ColorInfo, FormatType, ProjectionType, Quality, QualityLabel, SignatureCipher};
+use crate::VideoDetails;
+
+use super::Stream;
+
+/// A serializable snapshot of a [`Stream`]s metadata.
+///
+/// Contains everything a [`Stream`] knows about itself, except for the [`Client`] used for
+/// requests and the [`VideoDetails`] of the video the [`Stream`] belongs to. This allows i.e. to
+/// cache stream listings on disk, and later turn them back into downloadable [`Stream`]s using
+/// [`Stream::from_info`].
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamInfo {
+    #[serde_as(as = "DisplayFromStr")]
+    pub mime: Mime,
+    pub codecs: Vec<String>,
+    pub is_progressive: bool,
+    pub includes_video_track: bool,
+    pub includes_audio_track: bool,
+    pub format_type: Option<FormatType>,
+    pub approx_duration_ms: Option<u64>,
+    pub audio_channels: Option<u8>,
+    pub audio_quality: Option<AudioQuality>,
+    pub audio_sample_rate: Option<u64>,
+    pub average_bitrate: Option<u64>,
+    pub bitrate: Option<u64>,
+    pub color_info: Option<ColorInfo>,
+    /// The content length, if it was known when the snapshot was taken.
+    pub content_length: Option<u64>,
+    pub fps: u8,
+    pub height: Option<u64>,
+    pub high_replication: Option<bool>,
+    pub index_range: Option<Range<u64>>,
+    pub init_range: Option<Range<u64>>,
+    pub is_otf: bool,
+    pub itag: u64,
+    #[serde(with = "crate::serde_impl::unix_timestamp_micro_secs")]
+    pub last_modified: DateTime<Utc>,
+    pub loudness_db: Option<f64>,
+    pub projection_type: ProjectionType,
+    pub quality: Quality,
+    pub quality_label: Option<QualityLabel>,
+    pub signature_cipher: SignatureCipher,
+    pub width: Option<u64>,
+}
+
+impl From<&Stream> for StreamInfo {
+    fn from(stream: &Stream) -> Self {
+        Self {
+            mime: stream.mime.clone(),
+            codecs: stream.codecs.clone(),
+            is_progressive: stream.is_progressive,
+            includes_video_track: stream.includes_video_track,
+            includes_audio_track: stream.includes_audio_track,
+            format_type: stream.format_type,
+            approx_duration_ms: stream.approx_duration_ms,
+            audio_channels: stream.audio_channels,
+            audio_quality: stream.audio_quality,
+            audio_sample_rate: stream.audio_sample_rate,
+            average_bitrate: stream.average_bitrate,
+            bitrate: stream.bitrate,
+            color_info: stream.color_info.clone(),
+            content_length: stream.content_length.get().copied(),
+            fps: stream.fps,
+            height: stream.height,
+            high_replication: stream.high_replication,
+            index_range: stream.index_range.clone(),
+            init_range: stream.init_range.clone(),
+            is_otf: stream.is_otf,
+            itag: stream.itag,
+            last_modified: stream.last_modified,
+            loudness_db: stream.loudness_db,
+            projection_type: stream.projection_type,
+            quality: stream.quality,
+            quality_label: stream.quality_label,
+            signature_cipher: stream.signature_cipher.clone(),
+            width: stream.width,
+        }
+    }
+}
+
+/// The content of a sidecar file written by [`Stream::write_sidecar`].
+#[cfg(any(feature = "download", doc))]
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Sidecar<'a> {
+    stream: StreamInfo,
+    video_details: &'a VideoDetails,
+}
+
+#[doc(cfg(feature = "serde"))]
+impl Stream {
+    /// Writes a JSON sidecar file next to the downloaded file at `path`, which describes the
+    /// [`Stream`] ([`Stream::info`]) and the video it belongs to ([`VideoDetails`]).
+    /// The sidecar is written to `<path>.json`, which is returned.
+    ///
+    /// This makes downloads self-documenting, i.e. for archival.
+    #[cfg(any(feature = "download", doc))]
+    #[doc(cfg(all(feature = "serde", feature = "download")))]
+    pub async fn write_sidecar<P: AsRef<Path>>(&self, path: P) -> crate::Result<PathBuf> {
+        let mut sidecar_path = path.as_ref().as_os_str().to_owned();
+        sidecar_path.push(".json");
+        let sidecar_path = PathBuf::from(sidecar_path);
+
+        let sidecar = Sidecar {
+            stream: self.info(),
+            video_details: &self.video_details,
+        };
+        tokio::fs::write(&sidecar_path, serde_json::to_vec_pretty(&sidecar)?).await?;
+        log::debug!("wrote the sidecar of {} to {:?}", self.video_details.video_id, sidecar_path);
+
+        Ok(sidecar_path)
+    }
+
+    /// Takes a serializable snapshot of the [`Stream`]s metadata.
+    #[inline]
+    pub fn info(&self) -> StreamInfo {
+        StreamInfo::from(self)
+    }
+
+    /// Turns a [`StreamInfo`] back into a downloadable [`Stream`].
+    ///
+    /// Keep in mind, that the URLs of a [`Stream`] expire after a while. So a [`Stream`]
+    /// rehydrated from an old [`StreamInfo`] may not be downloadable anymore.
+    pub fn from_info(info: StreamInfo, client: Client, video_details: Arc<VideoDetails>) -> Self {
+        Self {
+            mime: info.mime,
+            codecs: info.codecs,
+            is_progressive: info.is_progressive,
+            includes_video_track: info.includes_video_track,
+            includes_audio_track: info.includes_audio_track,
+            format_type: info.format_type,
+            approx_duration_ms: info.approx_duration_ms,
+            audio_channels: info.audio_channels,
+            audio_quality: info.audio_quality,
+            audio_sample_rate: info.audio_sample_rate,
+            average_bitrate: info.average_bitrate,
+            bitrate: info.bitrate,
+            color_info: info.color_info,
+            content_length: Arc::new(OnceCell::new_with(info.content_length)),
+            accepts_ranges: Arc::new(OnceCell::new()),
+            fps: info.fps,
+            height: info.height,
+            high_replication: info.high_replication,
+            index_range: info.index_range,
+            init_range: info.init_range,
+            is_otf: info.is_otf,
+            itag: info.itag,
+            last_modified: info.last_modified,
+            loudness_db: info.loudness_db,
+            projection_type: info.projection_type,
+            quality: info.quality,
+            quality_label: info.quality_label,
+            signature_cipher: info.signature_cipher,
+            width: info.width,
+            video_details,
+            client,
+            client_config: None,
+            proxy_pool: None,
+            user_agent: None,
+            headers: None,
+            sequenced_fallback: true,
+            read_buffer_size: 0,
+            sequenced_window: super::SEQUENCED_WINDOW,
+            observer: None,
+            fallback_urls: None,
+            raw_format: None,
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tokio::io::AsyncWrite;
+
+/// An [`AsyncWrite`], that refuses to write more than `max_bytes` bytes to the inner writer.
+///
+/// Writes exceeding `max_bytes` fail, and set [`Limited::exceeded`], so the resulting io error
+/// can be told apart from others. Without a limit, all writes are passed through unchanged.
+pub(super) struct Limited<W> {
+    inner: W,
+    max_bytes: Option<u64>,
+    written: u64,
+    exceeded: bool,
+}
+
+impl<W> Limited<W> {
+    #[inline]
+    pub(super) fn new(inner: W, max_bytes: Option<u64>) -> Self {
+        Self {
+            inner,
+            max_bytes,
+            written: 0,
+            exceeded: false,
+        }
+    }
+
+    #[inline]
+    pub(super) fn exceeded(&self) -> bool {
+        self.exceeded
+    }
+
+    #[inline]
+    pub(super) fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: AsyncWrite + Unpin> AsyncWrite for Limited<W> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        if let Some(max_bytes) = this.max_bytes {
+            if this.written + buf.len() as u64 > max_bytes {
+                this.exceeded = true;
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    format!("the download exceeded its maximum size of {} bytes", max_bytes),
+                )));
+            }
+        }
+
+        let written = Pin::new(&mut this.inner).poll_write(cx, buf);
+        if let Poll::Ready(Ok(n)) = written {
+            this.written += n as u64;
+        }
+        written
+    }
+
+    #[inline]
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    #[inline]
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_shutdown(cx)
+    }
+}
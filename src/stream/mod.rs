@@ -1,48 +1,194 @@
+#[cfg(any(feature = "download", doc))]
+#[doc(cfg(feature = "download"))]
+use std::future::Future;
+#[cfg(any(feature = "download", doc))]
+#[doc(cfg(feature = "download"))]
+use std::lazy::SyncLazy;
 use std::ops::Range;
 #[cfg(any(feature = "download", doc))]
 #[doc(cfg(feature = "download"))]
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::sync::atomic::{AtomicU64, Ordering};
+#[cfg(any(feature = "download", doc))]
+#[doc(cfg(feature = "download"))]
+use std::time::Duration;
 
 use chrono::{DateTime, Utc};
 use mime::Mime;
 use reqwest::Client;
+use tokio::sync::OnceCell;
 #[cfg(any(feature = "download", doc))]
 #[doc(cfg(feature = "download"))]
 use tokio::{
     fs::File,
-    io::AsyncWriteExt,
+    io::{AsyncWrite, AsyncWriteExt, BufWriter},
 };
 #[cfg(any(feature = "callback", doc))]
 #[doc(cfg(feature = "callback"))]
-use tokio::{sync::mpsc::error::TrySendError, task};
+use tokio::sync::mpsc::error::TrySendError;
 #[cfg(any(feature = "download", doc))]
 #[doc(cfg(feature = "download"))]
 use tokio_stream::StreamExt;
+#[cfg(any(feature = "download", doc))]
+#[doc(cfg(feature = "download"))]
+use tokio_util::sync::CancellationToken;
 
 #[cfg(any(feature = "callback", doc))]
 #[doc(cfg(feature = "callback"))]
-use callback::Callback;
+use callback::{Callback, ProgressEvent};
 
 #[cfg(any(feature = "download", doc))]
 #[doc(cfg(feature = "download"))]
 use crate::{Error, Result};
-use crate::video_info::player_response::streaming_data::{AudioQuality, ColorInfo, FormatType, ProjectionType, Quality, QualityLabel, RawFormat, SignatureCipher};
+use crate::video_info::player_response::streaming_data::{
+    AudioQuality, ColorInfo, ColorInfoPrimary, FormatType, MatrixCoefficients, ProjectionType, Quality,
+    QualityLabel, RawFormat, SignatureCipher, TransferCharacteristics,
+};
 use crate::VideoDetails;
 
 #[cfg(any(feature = "callback", doc))]
 #[doc(cfg(feature = "callback"))]
 pub mod callback;
+#[cfg(any(feature = "download", doc))]
+#[doc(cfg(feature = "download"))]
+mod download_options;
+#[cfg(any(feature = "download", doc))]
+#[doc(cfg(feature = "download"))]
+mod fan_out;
+#[cfg(any(feature = "download", doc))]
+#[doc(cfg(feature = "download"))]
+mod limited;
+#[cfg(any(feature = "download", doc))]
+#[doc(cfg(feature = "download"))]
+mod pause;
+#[cfg(any(feature = "serde", doc))]
+#[doc(cfg(feature = "serde"))]
+mod info;
+#[cfg(any(feature = "mux", doc))]
+#[doc(cfg(feature = "mux"))]
+mod mux;
+mod observer;
+mod proxy_pool;
+#[cfg(any(feature = "download", doc))]
+#[doc(cfg(feature = "download"))]
+mod segment_progress;
+#[cfg(any(feature = "download", doc))]
+#[doc(cfg(feature = "download"))]
+mod throttle;
+
+#[cfg(any(feature = "serde", doc))]
+#[doc(cfg(feature = "serde"))]
+pub use info::StreamInfo;
+#[cfg(any(feature = "download", doc))]
+#[doc(cfg(feature = "download"))]
+pub use download_options::{DownloadOptions, OverwritePolicy};
+#[cfg(any(feature = "download", doc))]
+#[doc(cfg(feature = "download"))]
+pub use pause::PauseHandle;
+pub use observer::DownloadObserver;
+pub use proxy_pool::ProxyPool;
 
 // todo: 
 //  there are different types of streams: video, audio, and video + audio
 //  make Stream and RawFormat an enum, so there are less options in it
 
 #[cfg(not(any(feature = "callback", doc)))]
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Callback {}
 
+/// The kind of tracks a [`Stream`] contains.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StreamKind {
+    /// The [`Stream`] contains both a video and an audio track.
+    Progressive,
+    /// The [`Stream`] contains only a video track.
+    VideoOnly,
+    /// The [`Stream`] contains only an audio track.
+    AudioOnly,
+    /// The [`Stream`] claims to contain neither a video nor an audio track, so its kind is
+    /// unknown.
+    Unknown,
+}
+
+/// The properties of a [`Stream`]s audio track, returned by [`Stream::audio_info`].
+///
+/// The [`Display`](std::fmt::Display) implementation joins all known properties to a one-line
+/// descriptor, i.e. `AUDIO_QUALITY_MEDIUM, 48kHz, stereo`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AudioInfo {
+    /// The quality YouTube assigned to the audio track.
+    pub quality: Option<AudioQuality>,
+    /// The sample rate in Hz.
+    pub sample_rate: Option<u64>,
+    /// The number of audio channels.
+    pub channels: Option<u8>,
+}
+
+impl std::fmt::Display for AudioInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let quality = self.quality.map(|quality| match quality {
+            AudioQuality::Low => "AUDIO_QUALITY_LOW".to_owned(),
+            AudioQuality::Medium => "AUDIO_QUALITY_MEDIUM".to_owned(),
+            AudioQuality::High => "AUDIO_QUALITY_HIGH".to_owned(),
+        });
+        // 48000 ⇒ 48kHz, 44100 ⇒ 44.1kHz
+        let sample_rate = self.sample_rate.map(|sample_rate| format!("{}kHz", sample_rate as f64 / 1000.));
+        let channels = self.channels.map(|channels| match channels {
+            1 => "mono".to_owned(),
+            2 => "stereo".to_owned(),
+            channels => format!("{} channels", channels),
+        });
+
+        let parts: Vec<_> = vec![quality, sample_rate, channels]
+            .into_iter()
+            .flatten()
+            .collect();
+        f.write_str(&parts.join(", "))
+    }
+}
+
+/// The result of [`Stream::probe`].
+#[cfg(any(feature = "download", doc))]
+#[doc(cfg(feature = "download"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ProbeResult {
+    /// The status code YouTube responded with.
+    pub status: reqwest::StatusCode,
+    /// The content length of the [`Stream`], if the response contained it.
+    pub content_length: Option<u64>,
+    /// Whether or not the server supports `Range` requests (`Accept-Ranges: bytes`).
+    pub accepts_ranges: bool,
+}
+
+/// The result of a conditional download, i.e. [`Stream::download_to_if_modified_since`].
+#[cfg(any(feature = "download", doc))]
+#[doc(cfg(feature = "download"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DownloadOutcome {
+    /// The resource was modified, and downloaded. Contains the number of bytes written.
+    Downloaded(u64),
+    /// The resource was not modified, so nothing was downloaded.
+    NotModified,
+}
+
+/// The result of [`Stream::head_info`].
+#[cfg(any(feature = "download", doc))]
+#[doc(cfg(feature = "download"))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HeadInfo {
+    /// The content length of the [`Stream`], if the response contained it.
+    pub content_length: Option<u64>,
+    /// When the resource was last modified (`Last-Modified`), if the response contained it.
+    pub last_modified: Option<DateTime<Utc>>,
+    /// The mime type of the resource (`Content-Type`), if the response contained it.
+    pub content_type: Option<Mime>,
+    /// Whether or not the server supports `Range` requests (`Accept-Ranges: bytes`).
+    pub accepts_ranges: bool,
+    /// The file name the server suggests (`Content-Disposition`), if any.
+    /// Any directories are stripped, and characters, which are invalid in file names, replaced.
+    pub suggested_file_name: Option<String>,
+}
+
 /// A downloadable video Stream, that contains all the important information. 
 #[derive(Clone, derivative::Derivative)]
 #[derivative(Debug, PartialEq)]
@@ -60,10 +206,16 @@ pub struct Stream {
     pub average_bitrate: Option<u64>,
     pub bitrate: Option<u64>,
     pub color_info: Option<ColorInfo>,
-    #[derivative(PartialEq(compare_with = "atomic_u64_is_eq"))]
-    content_length: Arc<AtomicU64>,
+    #[derivative(PartialEq(compare_with = "once_cell_is_eq"))]
+    content_length: Arc<OnceCell<u64>>,
+    #[derivative(Debug = "ignore", PartialEq = "ignore")]
+    accepts_ranges: Arc<OnceCell<bool>>,
     pub fps: u8,
     pub height: Option<u64>,
+    /// Whether or not YouTube replicates the [`Stream`] to more of its edge servers than usual,
+    /// which it does for popular formats. Such [`Stream`]s are more likely to be served from a
+    /// nearby edge, and may therefore download faster (see [`Stream::prefer_high_replication`]).
+    /// `None`, if YouTube didn't say.
     pub high_replication: Option<bool>,
     pub index_range: Option<Range<u64>>,
     pub init_range: Option<Range<u64>>,
@@ -79,14 +231,597 @@ pub struct Stream {
     pub video_details: Arc<VideoDetails>,
     #[derivative(Debug = "ignore", PartialEq = "ignore")]
     client: Client,
+    #[derivative(Debug = "ignore", PartialEq = "ignore")]
+    client_config: Option<crate::fetcher::ClientConfig>,
+    #[derivative(Debug = "ignore", PartialEq = "ignore")]
+    proxy_pool: Option<Arc<ProxyPool>>,
+    #[derivative(PartialEq = "ignore")]
+    user_agent: Option<Arc<str>>,
+    #[derivative(PartialEq = "ignore")]
+    headers: Option<Arc<reqwest::header::HeaderMap>>,
+    #[derivative(PartialEq = "ignore")]
+    sequenced_fallback: bool,
+    #[derivative(PartialEq = "ignore")]
+    read_buffer_size: usize,
+    #[derivative(PartialEq = "ignore")]
+    sequenced_window: usize,
+    #[derivative(Debug = "ignore", PartialEq = "ignore")]
+    observer: Option<Arc<dyn DownloadObserver>>,
+    #[derivative(PartialEq = "ignore")]
+    fallback_urls: Option<Arc<[url::Url]>>,
+    #[derivative(Debug = "ignore", PartialEq = "ignore")]
+    raw_format: Option<Arc<RawFormat>>,
 }
 
 
 impl Stream {
+    /// The [`StreamKind`] of the [`Stream`], derived from
+    /// [`includes_video_track`](Stream::includes_video_track) and
+    /// [`includes_audio_track`](Stream::includes_audio_track).
+    #[inline]
+    pub fn kind(&self) -> StreamKind {
+        match (self.includes_video_track, self.includes_audio_track) {
+            (true, true) => StreamKind::Progressive,
+            (true, false) => StreamKind::VideoOnly,
+            (false, true) => StreamKind::AudioOnly,
+            (false, false) => StreamKind::Unknown,
+        }
+    }
+
+    /// The [`Stream`] of the given [`StreamKind`] with the best quality.
+    ///
+    /// [`Stream`]s are ranked by their [`quality_rank`](Stream::quality_rank).
+    #[inline]
+    pub fn best_quality(streams: &[Stream], kind: StreamKind) -> Option<&Stream> {
+        streams
+            .iter()
+            .filter(|stream| stream.kind() == kind)
+            .max_by_key(|stream| stream.quality_rank())
+    }
+
+    /// The [`Stream`] of the given [`StreamKind`] with the worst quality.
+    ///
+    /// [`Stream`]s are ranked the same way as in [`Stream::best_quality`].
+    #[inline]
+    pub fn worst_quality(streams: &[Stream], kind: StreamKind) -> Option<&Stream> {
+        streams
+            .iter()
+            .filter(|stream| stream.kind() == kind)
+            .min_by_key(|stream| stream.quality_rank())
+    }
+
+    /// Picks a [`Stream`] the way a player's "auto" quality would, since YouTube doesn't mark any
+    /// format as the default one.
+    ///
+    /// Progressive [`Stream`]s are preferred, since they can be played right away. Among them,
+    /// the best [`Stream`] with a height of at most 720p is picked, or, if all of them are larger,
+    /// the smallest one. Only if there are no progressive [`Stream`]s, video-only ones are
+    /// considered the same way. [VR](Stream::is_vr) [`Stream`]s are never picked.
+    pub fn auto_default(streams: &[Stream]) -> Option<&Stream> {
+        let pick = |kind: StreamKind| {
+            let candidates = streams
+                .iter()
+                .filter(move |stream| stream.kind() == kind && !stream.is_vr());
+            candidates
+                .clone()
+                .filter(|stream| stream.height.map_or(false, |h| h <= AUTO_DEFAULT_HEIGHT))
+                .max_by_key(|stream| stream.quality_rank())
+                .or_else(|| {
+                    candidates.min_by_key(|stream| {
+                        (stream.height, std::cmp::Reverse(stream.quality_rank()))
+                    })
+                })
+        };
+
+        pick(StreamKind::Progressive).or_else(|| pick(StreamKind::VideoOnly))
+    }
+
+    /// All [`Stream`]s of the given [`StreamKind`].
+    #[inline]
+    pub fn filter_kind(streams: &[Stream], kind: StreamKind) -> impl Iterator<Item=&Stream> {
+        streams
+            .iter()
+            .filter(move |stream| stream.kind() == kind)
+    }
+
+    /// All [`Stream`]s, that contain a video track, i.e. both video-only and progressive ones.
+    /// For video-only [`Stream`]s, use [`Stream::filter_kind`] with [`StreamKind::VideoOnly`].
+    #[inline]
+    pub fn video_streams(streams: &[Stream]) -> impl Iterator<Item=&Stream> {
+        streams
+            .iter()
+            .filter(|stream| stream.includes_video_track)
+    }
+
+    /// All [`Stream`]s, that contain an audio track, i.e. both audio-only and progressive ones.
+    /// For audio-only [`Stream`]s, use [`Stream::filter_kind`] with [`StreamKind::AudioOnly`].
+    #[inline]
+    pub fn audio_streams(streams: &[Stream]) -> impl Iterator<Item=&Stream> {
+        streams
+            .iter()
+            .filter(|stream| stream.includes_audio_track)
+    }
+
+    /// All progressive [`Stream`]s, which contain both a video and an audio track.
+    #[inline]
+    pub fn progressive_streams(streams: &[Stream]) -> impl Iterator<Item=&Stream> {
+        Self::filter_kind(streams, StreamKind::Progressive)
+    }
+
+    /// All [`Stream`]s with at least `min_fps` frames per second, i.e. `60` for high framerate
+    /// videos. Audio-only [`Stream`]s have no framerate, and are therefore never included.
+    #[inline]
+    pub fn filter_min_fps(streams: &[Stream], min_fps: u8) -> impl Iterator<Item=&Stream> {
+        streams
+            .iter()
+            .filter(move |stream| stream.includes_video_track && stream.fps >= min_fps)
+    }
+
+    /// All [`Stream`]s, that can be played by a regular video player, i.e. that don't contain
+    /// [VR content](Stream::is_vr).
+    #[inline]
+    pub fn filter_non_vr(streams: &[Stream]) -> impl Iterator<Item=&Stream> {
+        streams
+            .iter()
+            .filter(|stream| !stream.is_vr())
+    }
+
+    /// All [`Stream`]s, except the ones, for which there's a
+    /// [high replication](Stream::high_replication) alternative of the same kind, container and
+    /// quality, which is likely to download faster.
+    ///
+    /// [`Stream`]s are of the same quality, if they share their [`quality`](Stream::quality),
+    /// [`height`](Stream::height) and [`audio_quality`](Stream::audio_quality).
+    #[inline]
+    pub fn prefer_high_replication(streams: &[Stream]) -> impl Iterator<Item=&Stream> {
+        let same_quality = |a: &Stream, b: &Stream| {
+            a.kind() == b.kind()
+                && a.mime == b.mime
+                && (a.quality, a.height, a.audio_quality) == (b.quality, b.height, b.audio_quality)
+        };
+        streams
+            .iter()
+            .filter(move |stream| {
+                stream.is_high_replication() || !streams
+                    .iter()
+                    .any(|other| other.is_high_replication() && same_quality(other, stream))
+            })
+    }
+
+    /// The [`Stream`] with the given [`itag`](Stream::itag), i.e. `251` for opus audio.
+    #[inline]
+    pub fn by_itag(streams: &[Stream], itag: u64) -> Option<&Stream> {
+        streams
+            .iter()
+            .find(|stream| stream.itag == itag)
+    }
+
+    /// Finds the audio-only [`Stream`] in `streams`, that contains the same audio as this
+    /// (progressive) [`Stream`].
+    ///
+    /// Downloading the audio-only [`Stream`] saves bandwidth, when you are only interested in the
+    /// audio of a progressive [`Stream`]. The known pairings of YouTube's progressive and adaptive
+    /// formats are preferred (i.e. itag 18 ⇒ 140). Otherwise, the audio-only [`Stream`] with the
+    /// same container and the closest audio quality is used.
+    /// Only [`Stream`]s of the same video are considered.
+    pub fn audio_only_equivalent<'a>(&self, streams: &'a [Stream]) -> Option<&'a Stream> {
+        let audio_only = streams
+            .iter()
+            .filter(|stream| stream.kind() == StreamKind::AudioOnly)
+            .filter(|stream| stream.video_details.video_id == self.video_details.video_id);
+
+        // the adaptive audio formats YouTube pairs with its progressive formats
+        let itags: &[u64] = match self.itag {
+            18 | 22 | 37 | 38 | 59 | 78 => &[140],
+            43 | 44 | 45 | 46 => &[171, 251],
+            _ => &[],
+        };
+
+        itags
+            .iter()
+            .find_map(|&itag| audio_only.clone().find(|stream| stream.itag == itag))
+            .or_else(|| {
+                audio_only
+                    .filter(|stream| stream.mime.subtype() == self.mime.subtype())
+                    .max_by_key(|stream| (stream.audio_quality == self.audio_quality, stream.bitrate))
+            })
+    }
+
+    /// Whether or not the [`Stream`] is known to be [highly replicated](Stream::high_replication).
+    #[inline]
+    pub fn is_high_replication(&self) -> bool {
+        self.high_replication == Some(true)
+    }
+
+    /// A rank of the [`Stream`]s quality, so [`Stream`]s can be sorted using
+    /// `streams.sort_by_key(Stream::quality_rank)`. A higher rank means a better quality.
+    ///
+    /// The rank compares, in this order of precedence:
+    /// 1. the video resolution ([`height`](Stream::height), then [`width`](Stream::width))
+    /// 2. the [`bitrate`](Stream::bitrate)
+    /// 3. the [`fps`](Stream::fps)
+    /// 4. the [`audio_sample_rate`](Stream::audio_sample_rate)
+    ///
+    /// Missing values rank lowest. Since audio-only [`Stream`]s have no resolution and no frame
+    /// rate, they are ranked below all video [`Stream`]s, and among themselves by their bitrate
+    /// and then by their sample rate.
+    #[inline]
+    pub fn quality_rank(&self) -> (u64, u64, u64, u8, u64) {
+        let (height, width, fps) = match self.includes_video_track {
+            true => (self.height.unwrap_or(0), self.width.unwrap_or(0), self.fps),
+            false => (0, 0, 0),
+        };
+
+        (height, width, self.bitrate.unwrap_or(0), fps, self.audio_sample_rate.unwrap_or(0))
+    }
+
+    /// Sends all requests of this [`Stream`] through the proxies of the provided [`ProxyPool`],
+    /// instead of using the [`Client`] the [`Stream`] was fetched with.
+    #[inline]
+    pub fn with_proxy_pool(mut self, proxy_pool: Arc<ProxyPool>) -> Self {
+        self.proxy_pool = Some(proxy_pool);
+        self
+    }
+
+    /// Clones the [`Stream`], but replaces the [`Client`] used for requests, i.e. to continue
+    /// with a new session or through another proxy, without fetching the video again.
+    /// Any [`ProxyPool`] is removed, so all requests are sent using `client`.
+    #[inline]
+    pub fn clone_with_client(&self, client: Client) -> Self {
+        Self {
+            client,
+            client_config: None,
+            proxy_pool: None,
+            ..self.clone()
+        }
+    }
+
+    /// Remembers what the [`Client`] of the [`Stream`] is made of, so it can be rebuilt with
+    /// another redirect policy (see [`DownloadOptions::max_redirects`]).
+    #[inline]
+    pub(crate) fn with_client_config(mut self, client_config: Option<crate::fetcher::ClientConfig>) -> Self {
+        self.client_config = client_config;
+        self
+    }
+
+    /// Provides alternate URLs of the [`Stream`]s resource, i.e. the same format served by another
+    /// CDN edge, which are tried in order, when a request to the [`url`](Stream::url) fails with
+    /// a connection error, a timeout, or a server error.
+    ///
+    /// For sequenced downloads, the sequence number of the failed request is added to the
+    /// fallback URL. YouTube only ever provides a single URL per format, so the fallback URLs
+    /// have to be obtained separately, i.e. by fetching the video again.
+    #[inline]
+    pub fn with_fallback_urls(mut self, fallback_urls: Vec<url::Url>) -> Self {
+        self.fallback_urls = Some(fallback_urls.into());
+        self
+    }
+
+    /// The fallback URLs provided with [`Stream::with_fallback_urls`].
+    #[inline]
+    pub fn fallback_urls(&self) -> &[url::Url] {
+        self.fallback_urls.as_deref().unwrap_or_default()
+    }
+
+    /// Reports the requests, the written bytes, the retries, and the outcome of downloads of this
+    /// [`Stream`] to the provided [`DownloadObserver`], i.e. to export metrics.
+    #[inline]
+    pub fn with_observer(mut self, observer: Arc<dyn DownloadObserver>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Sends all requests of this [`Stream`] with the provided `User-Agent` header, instead of the
+    /// one of the underlying [`Client`].
+    #[inline]
+    pub fn with_user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Sends all requests of this [`Stream`] with the provided additional headers, i.e. a
+    /// `Cookie` for age-restricted videos, or a `Referer`.
+    /// Headers, which are already set, are replaced. The `User-Agent` set using
+    /// [`Stream::with_user_agent`] takes precedence over one in `headers`.
+    #[inline]
+    pub fn with_headers(mut self, headers: reqwest::header::HeaderMap) -> Self {
+        self.headers = Some(Arc::new(headers));
+        self
+    }
+
+    /// Enables or disables the sequenced download, which is attempted, when YouTube responds
+    /// to a download with `404 Not Found` (enabled by default).
+    ///
+    /// Some adaptive streams can only be downloaded segment by segment, but a `404` may also mean,
+    /// that the URL of the [`Stream`] expired. With the fallback disabled, downloads fail fast
+    /// with the original error instead.
+    #[inline]
+    pub fn with_sequenced_fallback(mut self, enabled: bool) -> Self {
+        self.sequenced_fallback = enabled;
+        self
+    }
+
+    /// Collects received chunks, until at least `read_buffer_size` bytes are available, before
+    /// writing them, and reporting the progress (default: `0`, every chunk is written as soon as
+    /// it's received).
+    ///
+    /// The size of the chunks is up to the server and the network, and especially OTF streams
+    /// tend to arrive in many tiny chunks. Coalescing them reduces the number of writes and
+    /// progress updates.
+    #[inline]
+    pub fn with_read_buffer_size(mut self, read_buffer_size: usize) -> Self {
+        self.read_buffer_size = read_buffer_size;
+        self
+    }
+
+    /// The maximum number of segments of a sequenced download, that are requested concurrently
+    /// (default: `4`). A `window` of `0` is treated like `1`, which requests one segment after
+    /// the other.
+    ///
+    /// Regardless of the window, segments are written in the order of their sequence numbers.
+    /// Segments, which arrive early, are held in memory until all previous segments were written,
+    /// so at most `window` segments are held in memory at once. Streams, which don't advertise
+    /// their number of segments, are always requested one segment after the other.
+    #[inline]
+    pub fn with_sequenced_window(mut self, window: usize) -> Self {
+        self.sequenced_window = window.max(1);
+        self
+    }
+
+    /// The [`RawFormat`] this [`Stream`] was created from, including all the fields the [`Stream`]
+    /// doesn't model, or processes (i.e. the [`MimeType`](crate::video_info::player_response::streaming_data::MimeType)).
+    /// Useful to debug changes of YouTube's formats.
+    ///
+    /// The signature of the [`RawFormat`] is already deciphered. [`Stream`]s, that were not
+    /// created from a [`RawFormat`], i.e. the ones restored from a
+    /// [`StreamInfo`](crate::StreamInfo), return `None`.
+    #[inline]
+    pub fn raw_format(&self) -> Option<&RawFormat> {
+        self.raw_format.as_deref()
+    }
+
+    /// The URL the [`Stream`]s resource can be downloaded from.
+    ///
+    /// [`VideoDescrambler::descramble`](crate::VideoDescrambler::descramble) already applied the
+    /// deciphered signature to this URL, so it's ready to be fetched, i.e. by an external
+    /// downloader (as long as it didn't expire yet).
+    #[inline]
+    pub fn url(&self) -> &url::Url {
+        &self.signature_cipher.url
+    }
+
+    /// A stable key for the media resource behind this [`Stream`], i.e. to deduplicate downloads.
+    ///
+    /// Two [`Stream`]s of the same video, with the same [`itag`](Stream::itag), and the same
+    /// version of the media (`lmt` and `clen` query parameters), have the same `resource_id`,
+    /// even if they were fetched at different times, and therefore have differently signed URLs.
+    /// The key is a 64 bit FNV-1a hash, formatted as 16 hex digits, and doesn't change across
+    /// versions of this crate.
+    pub fn resource_id(&self) -> String {
+        const STABLE_PARAMS: &[&str] = &["id", "itag", "lmt", "clen"];
+
+        let mut params = self.signature_cipher.url
+            .query_pairs()
+            .filter(|(key, _)| STABLE_PARAMS.contains(&key.as_ref()))
+            .collect::<Vec<_>>();
+        params.sort();
+
+        let mut key = format!(
+            "{}\0{}\0{}",
+            self.video_details.video_id, self.itag, self.signature_cipher.url.path()
+        );
+        for (k, v) in params {
+            key.push_str(&format!("\0{}={}", k, v));
+        }
+
+        // FNV-1a, since the hashers of std are not guaranteed to be stable
+        let hash = key
+            .bytes()
+            .fold(0xcbf29ce484222325_u64, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3));
+        format!("{:016x}", hash)
+    }
+
+    /// When the signed [`url`](Stream::url) of the [`Stream`] expires (usually ~6 hours after the
+    /// video was fetched), taken from its `expire` query parameter.
+    /// Requests to an expired URL are answered with `403 Forbidden`.
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        use chrono::TimeZone;
+
+        let expire = self.signature_cipher.url
+            .query_pairs()
+            .find(|(key, _)| key == "expire")?
+            .1
+            .parse::<i64>()
+            .ok()?;
+        Utc.timestamp_opt(expire, 0).single()
+    }
+
+    /// Whether or not the signed [`url`](Stream::url) of the [`Stream`] already expired.
+    /// [`Stream`]s without a known expiry are never considered expired.
+    #[inline]
+    pub fn is_expired(&self) -> bool {
+        self.expires_at().map_or(false, |expires_at| expires_at <= Utc::now())
+    }
+
+    /// Whether or not the [`Stream`]s video has a high framerate, i.e. 48 frames per second, or
+    /// more.
+    #[inline]
+    pub fn is_high_framerate(&self) -> bool {
+        self.fps >= HIGH_FRAMERATE
+    }
+
+    /// Whether or not the [`Stream`] contains a 360° video
+    /// (see [`ProjectionType::is_360`]).
+    #[inline]
+    pub fn is_360(&self) -> bool {
+        self.projection_type.is_360()
+    }
+
+    /// Whether or not the [`Stream`] contains VR content, i.e. a 360° video, or a VR180 video
+    /// (see [`ProjectionType::is_vr`]).
+    #[inline]
+    pub fn is_vr(&self) -> bool {
+        self.projection_type.is_vr()
+    }
+
+    /// The aspect ratio (`width / height`) of the [`Stream`]s video, i.e. `1.777…` for 16:9.
+    /// Values below `1.0` indicate vertical videos.
+    #[inline]
+    pub fn aspect_ratio(&self) -> Option<f64> {
+        match (self.width?, self.height?) {
+            (_, 0) => None,
+            (width, height) => Some(width as f64 / height as f64),
+        }
+    }
+
+    /// The aspect ratio of the [`Stream`]s video as a reduced fraction, i.e. `(16, 9)` for a
+    /// 1920x1080 video.
+    pub fn aspect_ratio_fraction(&self) -> Option<(u64, u64)> {
+        fn gcd(a: u64, b: u64) -> u64 {
+            if b == 0 { a } else { gcd(b, a % b) }
+        }
+
+        match (self.width?, self.height?) {
+            (0, _) | (_, 0) => None,
+            (width, height) => {
+                let gcd = gcd(width, height);
+                Some((width / gcd, height / gcd))
+            }
+        }
+    }
+
+    /// Whether or not the [`Stream`] contains HDR video, i.e. uses either the PQ (HDR10) or the
+    /// HLG transfer characteristics.
+    #[inline]
+    pub fn is_hdr(&self) -> bool {
+        self.transfer().map_or(false, |transfer| transfer.is_hdr())
+    }
+
+    /// The color primaries of the [`Stream`]s video, if known.
+    #[inline]
+    pub fn color_primaries(&self) -> Option<ColorInfoPrimary> {
+        self.color_info.as_ref()?.primaries
+    }
+
+    /// The transfer characteristics of the [`Stream`]s video, if known.
+    #[inline]
+    pub fn transfer(&self) -> Option<TransferCharacteristics> {
+        self.color_info.as_ref().map(|color_info| color_info.transfer_characteristics)
+    }
+
+    /// The matrix coefficients of the [`Stream`]s video, if known.
+    #[inline]
+    pub fn matrix(&self) -> Option<MatrixCoefficients> {
+        self.color_info.as_ref()?.matrix_coefficients
+    }
+
+    /// The [`bitrate`](Stream::bitrate) in megabits per second.
+    #[inline]
+    pub fn bitrate_mbps(&self) -> Option<f64> {
+        self.bitrate.map(|bitrate| bitrate as f64 / 1_000_000.)
+    }
+
+    /// The [`average_bitrate`](Stream::average_bitrate) in megabits per second.
+    #[inline]
+    pub fn average_bitrate_mbps(&self) -> Option<f64> {
+        self.average_bitrate.map(|average_bitrate| average_bitrate as f64 / 1_000_000.)
+    }
+
+    /// The approximate duration of the [`Stream`], derived from
+    /// [`approx_duration_ms`](Stream::approx_duration_ms).
+    #[inline]
+    pub fn duration(&self) -> Option<chrono::Duration> {
+        self.approx_duration_ms
+            .map(|ms| chrono::Duration::milliseconds(ms as i64))
+    }
+
+    /// A quick estimate of the [`Stream`]s size in bytes, that doesn't require any requests.
+    ///
+    /// If the exact content length is already known, it's returned directly. Otherwise, the size
+    /// is estimated from the [`average_bitrate`](Stream::average_bitrate) (or the
+    /// [`bitrate`](Stream::bitrate), if there's no average bitrate) and the
+    /// [`approx_duration_ms`](Stream::approx_duration_ms). For the exact size, have a look at
+    /// [`Stream::content_length`].
+    #[inline]
+    pub fn estimated_size(&self) -> Option<u64> {
+        if let Some(&content_length) = self.content_length.get() {
+            return Some(content_length);
+        }
+
+        let bitrate = self.average_bitrate.or(self.bitrate)?;
+        let duration_ms = self.approx_duration_ms?;
+        Some((bitrate as u128 * duration_ms as u128 / 8_000) as u64)
+    }
+
+    /// The [`audio_quality`](Stream::audio_quality), [`audio_sample_rate`](Stream::audio_sample_rate)
+    /// and [`audio_channels`](Stream::audio_channels) of the [`Stream`], grouped together.
+    /// `None`, if the [`Stream`] has no audio track.
+    #[inline]
+    pub fn audio_info(&self) -> Option<AudioInfo> {
+        self.includes_audio_track.then(|| AudioInfo {
+            quality: self.audio_quality,
+            sample_rate: self.audio_sample_rate,
+            channels: self.audio_channels,
+        })
+    }
+
+    /// A one-line descriptor of the [`Stream`]s audio track, i.e.
+    /// `AUDIO_QUALITY_MEDIUM, 48kHz, stereo` (see [`AudioInfo`]).
+    /// `None`, if the [`Stream`] has no audio track, or none of its properties are known.
+    #[inline]
+    pub fn audio_summary(&self) -> Option<String> {
+        self.audio_info()
+            .map(|audio_info| audio_info.to_string())
+            .filter(|summary| !summary.is_empty())
+    }
+
+    /// The URL of the [best thumbnail](VideoDetails::best_thumbnail) of the video the [`Stream`]
+    /// belongs to. Relative thumbnail URLs are resolved against `https://www.youtube.com`.
+    /// `None`, if the video has no thumbnails, or the URL is invalid.
+    #[inline]
+    pub fn thumbnail_url(&self) -> Option<url::Url> {
+        let thumbnail = self.video_details.best_thumbnail()?;
+        url::Url::parse("https://www.youtube.com")
+            .ok()?
+            .join(&thumbnail.url)
+            .ok()
+    }
+
+    /// The linear gain, that normalizes the [`Stream`]s audio to YouTube's reference loudness.
+    ///
+    /// [`loudness_db`](Stream::loudness_db) is the difference between the loudness of the audio
+    /// and the reference loudness in dB, so the gain is `10^(-loudness_db / 20)`. Multiplying the
+    /// samples by this gain applies ReplayGain-style normalization.
+    /// YouTube's own player only ever lowers the volume, so to mimic it, clamp the gain to `1.0`.
+    #[inline]
+    pub fn loudness_gain(&self) -> Option<f64> {
+        self.loudness_db.map(|db| 10f64.powf(-db / 20.))
+    }
+
+    /// The file extension matching the [`Stream`]s [`mime`](Stream::mime) type, without a leading
+    /// dot.
+    ///
+    /// This is usually the mime subtype (i.e. `video/webm` ⇒ `webm`, `video/mp4` ⇒ `mp4`), except
+    /// for audio-only [`Stream`]s, which players expect to have an audio extension:
+    /// - `audio/mp4` ⇒ `m4a`
+    /// - `audio/webm` with the opus codec ⇒ `opus`
+    /// - `audio/webm` with any other codec ⇒ `weba`
+    #[inline]
+    pub fn file_extension(&self) -> &str {
+        match (self.mime.type_(), self.mime.subtype()) {
+            (mime::AUDIO, mime::MP4) => "m4a",
+            (mime::AUDIO, subtype) if subtype == "webm" => {
+                if has_codec(&self.codecs, &["opus"]) { "opus" } else { "weba" }
+            }
+            (_, subtype) => subtype.as_str(),
+        }
+    }
+
     // maybe deserialize RawFormat seeded with client and VideoDetails
     pub(crate) fn from_raw_format(raw_format: RawFormat, client: Client, video_details: Arc<VideoDetails>) -> Self {
+        let raw = Arc::new(raw_format.clone());
         Self {
-            is_progressive: is_progressive(&raw_format.mime_type.codecs),
+            is_progressive: is_progressive(&raw_format.mime_type.codecs, &raw_format.mime_type.mime),
             includes_video_track: includes_video_track(&raw_format.mime_type.codecs, &raw_format.mime_type.mime),
             includes_audio_track: includes_audio_track(&raw_format.mime_type.codecs, &raw_format.mime_type.mime),
             mime: raw_format.mime_type.mime,
@@ -99,7 +834,8 @@ impl Stream {
             average_bitrate: raw_format.average_bitrate,
             bitrate: raw_format.bitrate,
             color_info: raw_format.color_info,
-            content_length: Arc::new(AtomicU64::new(raw_format.content_length.unwrap_or(0))),
+            content_length: Arc::new(OnceCell::new_with(raw_format.content_length)),
+            accepts_ranges: Arc::new(OnceCell::new()),
             fps: raw_format.fps,
             height: raw_format.height,
             high_replication: raw_format.high_replication,
@@ -115,12 +851,21 @@ impl Stream {
             signature_cipher: raw_format.signature_cipher,
             width: raw_format.width,
             client,
+            client_config: None,
+            proxy_pool: None,
+            user_agent: None,
+            headers: None,
+            sequenced_fallback: true,
+            read_buffer_size: 0,
+            sequenced_window: SEQUENCED_WINDOW,
+            observer: None,
+            fallback_urls: None,
+            raw_format: Some(raw),
             video_details,
         }
     }
 }
 
-// todo: download in ranges
 // todo: blocking download
 
 #[cfg(any(feature = "download", doc))]
@@ -134,37 +879,161 @@ impl Stream {
     /// - When the content length was not included in the [`RawFormat`], and the request fails.
     #[inline]
     pub async fn content_length(&self) -> Result<u64> {
-        let cl = self.content_length.load(Ordering::SeqCst);
-        if cl != 0 { return Ok(cl); }
+        // concurrent callers share the result of a single HEAD request
+        self.content_length
+            .get_or_try_init(|| async {
+                self.head()
+                    .await
+                    .and_then(|res| self.error_for_status(res))?
+                    .headers()
+                    .get(reqwest::header::CONTENT_LENGTH)
+                    .and_then(|cl| cl.to_str().ok())
+                    .and_then(|cl| cl.parse::<u64>().ok())
+                    .map(|cl| {
+                        log::trace!("content length of {:?} is {}", self, cl);
+                        cl
+                    })
+                    .ok_or_else(|| Error::UnexpectedResponse(
+                        "the response did not contain a valid content-length field".into()
+                    ))
+            })
+            .await
+            .map(|cl| *cl)
+    }
 
-        self.client
-            .head(self.signature_cipher.url.as_str())
-            .send()
-            .await?
-            .error_for_status()?
-            .headers()
+    /// Checks whether or not the [`Stream`] is downloadable, without downloading it.
+    ///
+    /// Performs a single `HEAD` request. Unsuccessful status codes are not treated as errors, but
+    /// are returned in the [`ProbeResult`], so i.e. expired [`Stream`]s can be detected.
+    /// If the response contains the content length, it's cached for [`Stream::content_length`].
+    ///
+    /// ### Errors
+    /// - When the request fails.
+    pub async fn probe(&self) -> Result<ProbeResult> {
+        let res = self.head().await?;
+        let headers = res.headers();
+
+        let content_length = headers
             .get(reqwest::header::CONTENT_LENGTH)
             .and_then(|cl| cl.to_str().ok())
             .and_then(|cl| cl.parse::<u64>().ok())
-            .map(|cl| {
-                log::trace!("content length of {:?} is {}", self, cl);
-                self.content_length.store(cl, Ordering::SeqCst);
-                cl
-            })
-            .ok_or_else(|| Error::UnexpectedResponse(
-                "the response did not contain a valid content-length field".into()
-            ))
-    }
+            .filter(|_| res.status().is_success());
+        if let Some(content_length) = content_length {
+            let _ = self.content_length.set(content_length);
+        }
 
-    /// Attempts to downloads the [`Stream`]s resource.
-    /// This will download the video to <video_id>.mp4 in the current working directory.
+        let accepts_ranges = accepts_ranges(headers);
+        if res.status().is_success() {
+            let _ = self.accepts_ranges.set(accepts_ranges);
+        }
+
+        Ok(ProbeResult {
+            status: res.status(),
+            content_length,
+            accepts_ranges,
+        })
+    }
+
+    /// Whether or not the [`Stream`]s resource can still be reached, i.e. to cull expired
+    /// [`Stream`]s from a cached list.
+    ///
+    /// Like [`Stream::probe`], this performs a single `HEAD` request, but any failure, be it an
+    /// unsuccessful status code (i.e. `403`, `404`, or `410`) or a failed request, is reported as
+    /// `false` instead of an error.
+    #[inline]
+    pub async fn is_available(&self) -> bool {
+        match self.probe().await {
+            Ok(probe) => probe.status.is_success(),
+            Err(e) => {
+                log::debug!("{} is not available: {}", self.video_details.video_id, e);
+                false
+            }
+        }
+    }
+
+    /// Collects the most important response headers of the [`Stream`]s resource, using a single
+    /// `HEAD` request.
+    ///
+    /// The content length, and whether or not the server supports `Range` requests, are cached
+    /// for [`Stream::content_length`] and [`Stream::supports_ranges`].
+    ///
+    /// ### Errors
+    /// - When the request fails, or YouTube responds with an unsuccessful status code.
+    pub async fn head_info(&self) -> Result<HeadInfo> {
+        let res = self.head().await.and_then(|res| self.error_for_status(res))?;
+        let headers = res.headers();
+        let header = |name| headers
+            .get(name)
+            .and_then(|value: &reqwest::header::HeaderValue| value.to_str().ok());
+
+        let content_length = header(reqwest::header::CONTENT_LENGTH)
+            .and_then(|cl| cl.parse::<u64>().ok());
+        if let Some(content_length) = content_length {
+            let _ = self.content_length.set(content_length);
+        }
+
+        let accepts_ranges = accepts_ranges(headers);
+        let _ = self.accepts_ranges.set(accepts_ranges);
+
+        Ok(HeadInfo {
+            content_length,
+            last_modified: header(reqwest::header::LAST_MODIFIED)
+                .and_then(|lm| DateTime::parse_from_rfc2822(lm).ok())
+                .map(|lm| lm.with_timezone(&Utc)),
+            content_type: header(reqwest::header::CONTENT_TYPE)
+                .and_then(|ct| ct.parse::<Mime>().ok()),
+            accepts_ranges,
+            suggested_file_name: header(reqwest::header::CONTENT_DISPOSITION)
+                .and_then(content_disposition_file_name),
+        })
+    }
+
+    /// Checks whether or not the server supports `Range` requests (`Accept-Ranges: bytes`) for
+    /// this [`Stream`], which is required for resuming, or splitting up a download.
+    ///
+    /// The first call performs a `HEAD` request, the result is cached for later calls (also by
+    /// [`Stream::probe`]).
+    ///
+    /// ### Errors
+    /// - When the request fails, or YouTube responds with an unsuccessful status code.
+    pub async fn supports_ranges(&self) -> Result<bool> {
+        self.accepts_ranges
+            .get_or_try_init(|| async {
+                let res = self.head().await.and_then(|res| self.error_for_status(res))?;
+                Ok::<_, Error>(accepts_ranges(res.headers()))
+            })
+            .await
+            .map(|ar| *ar)
+    }
+
+    /// Determines the content length of all provided `streams`, with at most `concurrency` `HEAD`
+    /// requests running at the same time.
+    ///
+    /// The content lengths are cached in the [`Stream`]s, so later calls to
+    /// [`Stream::content_length`] (and [`Stream::estimated_size`]) return immediately.
+    /// Returns the result of every [`Stream`] in the same order as `streams`.
+    /// A `concurrency` of `0` is treated like `1`.
+    pub async fn fill_content_lengths(streams: &[Stream], concurrency: usize) -> Vec<Result<u64>> {
+        let requests = streams
+            .iter()
+            .map(Stream::content_length);
+
+        futures::StreamExt::buffered(futures::stream::iter(requests), concurrency.max(1))
+            .collect()
+            .await
+    }
+
+    /// Attempts to downloads the [`Stream`]s resource.
+    /// This will download the video to <video_id>.f<itag>.<extension> in the current working
+    /// directory, where <extension> is the [`Stream::file_extension`]. Including the itag
+    /// prevents downloads of different [`Stream`]s of the same video from colliding.
     #[inline]
     pub async fn download(&self) -> Result<PathBuf> {
         self.internal_download(None).await
     }
 
     /// Attempts to downloads the [`Stream`]s resource.
-    /// This will download the video to <video_id>.mp4 in the current working directory.
+    /// This will download the video to <video_id>.f<itag>.<extension> in the current working directory.
     /// Takes an [`Callback`](crate::stream::callback::Callback)
     #[cfg(any(feature = "callback", doc))]
     #[doc(cfg(feature = "callback"))]
@@ -173,24 +1042,98 @@ impl Stream {
         self.internal_download(Some(callback)).await
     }
 
+    /// Downloads the `video` and the `audio` [`Stream`] concurrently, i.e. an adaptive pair,
+    /// which is going to be muxed later on. Both are downloaded to
+    /// <video_id>.f<itag>.<extension> in the current working directory, and their paths are
+    /// returned as `(video, audio)`.
+    ///
+    /// The progress of both downloads is reported to the single `callback`, as if it was one
+    /// download: [`current_chunk`](callback::CallbackArguments::current_chunk) is the sum of the
+    /// bytes both downloads wrote, and [`total_bytes`](callback::CallbackArguments::total_bytes)
+    /// the sum of both content lengths, if both are known. Once both downloads are done,
+    /// `on_complete` is called once, with the path of the video track.
+    ///
+    /// If one of the downloads fails, the other one still finishes, but the error is returned.
+    #[cfg(any(feature = "callback", doc))]
+    #[doc(cfg(feature = "callback"))]
+    pub async fn download_pair_callback(
+        video: &Stream,
+        audio: &Stream,
+        mut callback: Callback,
+    ) -> Result<(PathBuf, PathBuf)> {
+        let video_path = video.default_file_name(false);
+        let audio_path = audio.default_file_name(false);
+
+        let total_bytes = match (video.total_bytes().await, audio.total_bytes().await) {
+            (Some(video), Some(audio)) => Some(video + audio),
+            _ => None,
+        };
+        Self::on_start(std::mem::take(&mut callback.on_start), total_bytes).await;
+        let progress = Self::on_progress(
+            callback.internal_receiver.take().expect("Callback cannot be used twice"),
+            std::mem::take(&mut callback.on_progress),
+            total_bytes,
+            callback.progress_interval,
+        );
+
+        // Every track reports its own progress, which is merged into one total. Updates of both
+        // tracks may still arrive out of order, which the progress loop takes care of.
+        let sender = callback.internal_sender.take().expect("Callback cannot be used twice");
+        let aggregator = Arc::new(callback::ProgressAggregator::new(2));
+        let track_callback = |track: usize| {
+            let aggregator = Arc::clone(&aggregator);
+            let sender = sender.clone();
+            Callback::new().connect_on_progress_closure(move |args| {
+                let bytes = aggregator.update(track, args.current_chunk);
+                // like for single downloads, updates are dropped, if the channel is full
+                let _ = sender.try_send(bytes);
+            })
+        };
+        let (video_callback, audio_callback) = (track_callback(0), track_callback(1));
+
+        let (video_path_, audio_path_) = (&video_path, &audio_path);
+        let download = async move {
+            let (video_result, audio_result) = tokio::join!(
+                video.internal_download_to(video_path_, Some(video_callback), None, None),
+                audio.internal_download_to(audio_path_, Some(audio_callback), None, None),
+            );
+            let result = video_result.and_then(|video| Ok(video + audio_result?));
+            // the final count is sent separately, to guarantee the progress reaches 100%
+            if let Ok(bytes) = result {
+                let _ = sender.send(bytes).await;
+            }
+            // dropping the last sender closes the channel, which lets the progress loop finish
+            drop(sender);
+            result
+        };
+        let result = tokio::join!(download, progress).0;
+
+        if let Err(ref e) = result {
+            Self::on_error(std::mem::take(&mut callback.on_error), e);
+        }
+        let path = result.as_ref().ok().map(|_| video_path.clone());
+        Self::on_complete(std::mem::take(&mut callback.on_complete), path).await;
+
+        result.map(|_| (video_path, audio_path))
+    }
+
     #[inline]
     async fn internal_download(&self, callback: Option<Callback>) -> Result<PathBuf> {
-        let path = Path::new(self.video_details.video_id.as_str())
-            .with_extension("mp4");
-        self.internal_download_to(&path, callback)
+        let path = self.default_file_name(false);
+        self.internal_download_to(&path, callback, None, None)
             .await
             .map(|_| path)
     }
 
     /// Attempts to downloads the [`Stream`]s resource.
-    /// This will download the video to <video_id>.mp4 in the provided directory.
+    /// This will download the video to <video_id>.f<itag>.<extension> in the provided directory.
     #[inline]
     pub async fn download_to_dir<P: AsRef<Path>>(&self, dir: P) -> Result<PathBuf> {
         self.internal_download_to_dir(dir, None).await
     }
 
     /// Attempts to downloads the [`Stream`]s resource.
-    /// This will download the video to <video_id>.mp4 in the provided directory. 
+    /// This will download the video to <video_id>.f<itag>.<extension> in the provided directory. 
     /// Takes an [`Callback`](crate::stream::callback::Callback)
     #[cfg(any(feature = "callback", doc))]
     #[doc(cfg(feature = "callback"))]
@@ -209,20 +1152,211 @@ impl Stream {
         dir: P,
         callback: Option<Callback>
     ) -> Result<PathBuf> {
-        let mut path = dir
+        let path = dir
+            .as_ref()
+            .join(self.default_file_name(false));
+        self.internal_download_to(&path, callback, None, None)
+            .await
+            .map(|_| path)
+    }
+
+    /// Attempts to downloads the [`Stream`]s resource.
+    /// This will download the video to the provided directory, using a file name built from
+    /// `template`.
+    ///
+    /// The template may contain the following fields, which are replaced by the [`Stream`]s
+    /// values:
+    /// - `{title}`: The title of the video. Characters that are not allowed in file names are
+    /// replaced by `_`.
+    /// - `{video_id}`: The [`Id`](crate::Id) of the video.
+    /// - `{itag}`: The [`itag`](Stream::itag) of the [`Stream`].
+    /// - `{quality}`: The [`quality`](Stream::quality) of the [`Stream`], i.e. `hd720`.
+    ///
+    /// The [`file_extension`](Stream::file_extension) is appended automatically. So
+    /// `"{title} ({itag})"` will download a `video/webm` [`Stream`] to `<title> (<itag>).webm`, and
+    /// an `audio/webm` [`Stream`] with the opus codec to `<title> (<itag>).opus`.
+    #[inline]
+    pub async fn download_to_dir_templated<P: AsRef<Path>>(&self, dir: P, template: &str) -> Result<PathBuf> {
+        let path = dir
             .as_ref()
-            .join(self.video_details.video_id.as_str());
-        path.set_extension("mp4");
-        self.internal_download_to(&path, callback)
+            .join(format!("{}.{}", self.render_file_name(template), self.file_extension()));
+        self.internal_download_to(&path, None, None, None)
             .await
             .map(|_| path)
     }
 
+    /// Replaces all fields in `template`, as described in [`Stream::download_to_dir_templated`].
+    fn render_file_name(&self, template: &str) -> String {
+        let title = sanitize_file_name(&self.video_details.title);
+
+        template
+            .replace("{title}", title.trim())
+            .replace("{video_id}", self.video_details.video_id.as_str())
+            .replace("{itag}", &self.itag.to_string())
+            .replace("{quality}", &format!("{:?}", self.quality).to_lowercase())
+    }
+
+    /// Attempts to download all provided `streams` to the provided directory, with at most
+    /// `concurrency` downloads running at the same time.
+    ///
+    /// Every [`Stream`] is downloaded to <video_id>.f<itag>.<extension>, so [`Stream`]s of the
+    /// same video don't overwrite each other. Returns the result of every download in the same
+    /// order as `streams`. A failed download does not abort the other ones.
+    /// A `concurrency` of `0` is treated like `1`.
+    pub async fn download_all_to_dir<P: AsRef<Path>>(
+        streams: &[Stream],
+        dir: P,
+        concurrency: usize,
+    ) -> Vec<Result<PathBuf>> {
+        let dir = dir.as_ref();
+        let downloads = streams
+            .iter()
+            .map(|stream| async move {
+                let path = dir.join(stream.default_file_name(false));
+                stream
+                    .internal_download_to(&path, None, None, None)
+                    .await
+                    .map(|_| path)
+            });
+
+        futures::StreamExt::buffered(futures::stream::iter(downloads), concurrency.max(1))
+            .collect()
+            .await
+    }
+
+    /// Attempts to downloads the [`Stream`]s resource.
+    /// This will download the video to the provided file path.
+    /// Returns the number of bytes written to the file.
+    ///
+    /// The resource is first downloaded to `<path>.<pid>.part`, which is only renamed to `path` once the
+    /// download succeeded. So there's never a partially downloaded file at `path`.
+    ///
+    /// If nothing but an empty body is received, the download fails with [`Error::EmptyStream`],
+    /// unless the content length of the [`Stream`] is known to be `0`.
+    #[inline]
+    pub async fn download_to<P: AsRef<Path>>(&self, path: P) -> Result<u64> {
+        self.internal_download_to(path, None, None, None).await
+    }
+
+    /// Attempts to downloads the [`Stream`]s resource.
+    /// This will download the video to the provided file path, like [`Stream::download_to`], but
+    /// the file is opened by `open`, i.e. to wrap it in an encrypting writer.
+    ///
+    /// `open` is called with the path of the temporary `<path>.<pid>.part` file, and has to create
+    /// the file at exactly that path, since it's renamed to `path` once the download succeeded, or
+    /// removed, if it failed. Once all bytes were written, the writer is shut down, so it can
+    /// finalize its output. Returns the number of bytes written to the writer.
+    ///
+    /// ```no_run
+    ///# use rustube::Stream;
+    ///# async fn run(stream: Stream) -> rustube::Result<()> {
+    /// stream.download_to_with_opener("video.mp4", |part_path| {
+    ///     let file = std::fs::File::create(part_path)?;
+    ///     // i.e. wrap the file in an encrypting writer
+    ///     Ok(tokio::fs::File::from_std(file))
+    /// }).await?;
+    ///# Ok(())
+    ///# }
+    /// ```
+    pub async fn download_to_with_opener<P, W, F>(&self, path: P, open: F) -> Result<u64>
+        where
+            P: AsRef<Path>,
+            W: AsyncWrite + Unpin,
+            F: FnOnce(&Path) -> Result<W> {
+        let start = std::time::Instant::now();
+        let open = move |part_path: PathBuf| futures::future::ready(open(&part_path));
+        let result = self
+            .download_via_part_file(path, DownloadOptions::new(), DownloadBody::Full, open)
+            .await;
+        self.observe_finish(&result, start);
+        result
+    }
+
+    /// Attempts to downloads the [`Stream`]s resource.
+    /// This will download the video to all of the provided file paths at once, while only
+    /// fetching it once.
+    ///
+    /// If writing to any of the files fails, all partially downloaded files are removed, and the
+    /// error is returned. The same goes for moving the files to their paths, once the download
+    /// finished: if moving any of them fails, the ones already moved are removed as well.
+    pub async fn download_to_many<P: AsRef<Path>>(&self, paths: &[P]) -> Result<u64> {
+        log::trace!("download_to_many: {:?}", paths.iter().map(AsRef::as_ref).collect::<Vec<_>>());
+        let part_paths: Vec<PathBuf> = paths
+            .iter()
+            .map(|path| part_path(path.as_ref()))
+            .collect();
+
+        let mut moved = 0;
+        let download = async {
+            let mut files = Vec::with_capacity(part_paths.len());
+            for part_path in part_paths.iter() {
+                let file = File::create(part_path).await?;
+                files.push(BufWriter::with_capacity(DEFAULT_WRITE_BUFFER_SIZE, file));
+            }
+
+            let mut writer = fan_out::FanOut::new(files);
+            let bytes = self.internal_download_to_writer(&mut writer, &None).await?;
+
+            for (part_path, path) in part_paths.iter().zip(paths) {
+                tokio::fs::rename(part_path, path).await?;
+                moved += 1;
+            }
+            Ok(bytes)
+        };
+
+        let result = download.await;
+        match result {
+            Ok(bytes) => log::info!(
+                "downloaded {} bytes of {} successfully to {} files",
+                bytes, self.video_details.video_id, paths.len()
+            ),
+            Err(ref e) => {
+                log::error!("failed to download {} to many files: {:?}", self.video_details.video_id, e);
+                for part_path in part_paths.iter() {
+                    // files that were already renamed, or never created, don't exist anymore
+                    let _ = tokio::fs::remove_file(part_path).await;
+                }
+                for path in paths[..moved].iter() {
+                    remove_part_file(path.as_ref()).await;
+                }
+            }
+        }
+        result
+    }
+
+    /// Attempts to downloads the [`Stream`]s resource.
+    /// This will download the video to the provided file path.
+    ///
+    /// Received chunks are buffered, and only written to the file, once `write_buffer_size`
+    /// bytes are collected. [`Stream::download_to`] uses a buffer of 64 KiB. Larger buffers mean
+    /// less syscalls, which especially helps with OTF streams, whose chunks tend to be tiny.
+    /// Progress is still reported for every chunk.
+    #[inline]
+    pub async fn download_to_buffered<P: AsRef<Path>>(&self, path: P, write_buffer_size: usize) -> Result<u64> {
+        let options = DownloadOptions::new().write_buffer_size(write_buffer_size);
+        self.internal_download_to_options(path, options).await
+    }
+
+    /// Attempts to downloads the [`Stream`]s resource.
+    /// This will download the video to the provided file path.
+    ///
+    /// If the download doesn't finish within `timeout`, it's aborted, the partially downloaded
+    /// file is removed, and [`Error::Timeout`] is returned.
+    #[inline]
+    pub async fn download_to_with_timeout<P: AsRef<Path>>(&self, path: P, timeout: Duration) -> Result<u64> {
+        self.internal_download_to(path, None, Some(timeout), None).await
+    }
+
     /// Attempts to downloads the [`Stream`]s resource.
     /// This will download the video to the provided file path.
+    ///
+    /// All requests are sent using the provided [`Client`], instead of the one the [`Stream`] was
+    /// fetched with. This allows i.e. to download a particular [`Stream`] through a different proxy.
     #[inline]
-    pub async fn download_to<P: AsRef<Path>>(&self, path: P) -> Result<()> {
-        self.internal_download_to(path, None).await
+    pub async fn download_to_with_client<P: AsRef<Path>>(&self, path: P, client: &Client) -> Result<u64> {
+        self.clone_with_client(client.clone())
+            .internal_download_to(path, None, None, None)
+            .await
     }
 
     /// Attempts to downloads the [`Stream`]s resource.
@@ -231,41 +1365,966 @@ impl Stream {
     #[cfg(any(feature = "callback", doc))]
     #[doc(cfg(feature = "callback"))]
     #[inline]
-    pub async fn download_to_callback<P: AsRef<Path>>(&self, path: P, callback: Callback) -> Result<()> {
-        self.internal_download_to(path, Some(callback)).await
+    pub async fn download_to_callback<P: AsRef<Path>>(&self, path: P, callback: Callback) -> Result<u64> {
+        self.internal_download_to(path, Some(callback), None, None).await
+    }
+
+    /// Attempts to downloads the [`Stream`]s resource.
+    /// This will download the video to the provided file path.
+    ///
+    /// Instead of taking a [`Callback`], the progress is reported as a
+    /// [`Stream`](tokio_stream::Stream) of [`ProgressEvent`]s, which can i.e. be polled in a
+    /// `select!` loop. The download only makes progress while the returned stream is polled.
+    /// The last event is always a [`ProgressEvent::Completed`], which contains the result of the
+    /// download, also if the download failed. It's only yielded once all progress events were
+    /// yielded and the progress channel was closed, so the stream ends right after it, and
+    /// `while let Some(event) = events.next().await` terminates exactly when the download is
+    /// done. The stream is fused, so polling it after it ended keeps returning `None`.
+    ///
+    /// The returned stream is not [`Unpin`], so it has to be pinned before polling it,
+    /// i.e. using [`tokio::pin!`].
+    #[cfg(any(feature = "callback", doc))]
+    #[doc(cfg(feature = "callback"))]
+    pub fn download_to_progress<'a, P: AsRef<Path> + 'a>(
+        &'a self,
+        path: P,
+    ) -> impl tokio_stream::Stream<Item=ProgressEvent> + futures::stream::FusedStream + 'a {
+        let (tx, rx) = tokio::sync::mpsc::channel(Callback::DEFAULT_CAPACITY);
+        let callback = Callback::new().connect_on_progress_sender(tx, false);
+
+        let download = Box::pin(self.internal_download_to(path, Some(callback), None, None));
+        let events = futures::stream::select(
+            tokio_stream::wrappers::ReceiverStream::new(rx).map(ProgressEvent::Progress),
+            futures::stream::once(download).map(ProgressEvent::Completed),
+        );
+
+        // The download may finish before all progress events were yielded, so the completion is
+        // held back until the progress channel is closed.
+        let events = futures::stream::unfold((events, None), |(mut events, mut completed)| async move {
+            loop {
+                match events.next().await {
+                    Some(event @ ProgressEvent::Progress(_)) => return Some((event, (events, completed))),
+                    Some(event @ ProgressEvent::Completed(_)) => completed = Some(event),
+                    None => return completed.map(|event| (event, (events, None))),
+                }
+            }
+        });
+        futures::StreamExt::fuse(events)
+    }
+
+    /// Attempts to downloads the [`Stream`]s resource.
+    /// This will download the video to the provided file path.
+    ///
+    /// When the `token` gets cancelled, the download is stopped, the partially downloaded file is
+    /// removed, and [`Error::Cancelled`] is returned.
+    #[inline]
+    pub async fn download_to_cancellable<P: AsRef<Path>>(
+        &self,
+        path: P,
+        token: CancellationToken,
+    ) -> Result<u64> {
+        self.internal_download_to(path, None, None, Some(token)).await
+    }
+
+    /// Attempts to downloads the [`Stream`]s resource.
+    /// This will download the video to the provided file path.
+    ///
+    /// When the `token` gets cancelled, the download is stopped, the partially downloaded file is
+    /// removed, and [`Error::Cancelled`] is returned. The `on_complete` callback receives `None`
+    /// in this case.
+    /// Takes an [`Callback`](crate::stream::callback::Callback)
+    #[cfg(any(feature = "callback", doc))]
+    #[doc(cfg(feature = "callback"))]
+    #[inline]
+    pub async fn download_to_cancellable_callback<P: AsRef<Path>>(
+        &self,
+        path: P,
+        callback: Callback,
+        token: CancellationToken,
+    ) -> Result<u64> {
+        self.internal_download_to(path, Some(callback), None, Some(token)).await
+    }
+
+    /// Attempts to downloads the [`Stream`]s resource.
+    /// This will download the video to the provided file path.
+    ///
+    /// The download can be paused and resumed using the `handle`. While paused, the connection
+    /// is dropped, so it cannot time out. On resume, the download continues where it stopped,
+    /// using a `Range` request.
+    ///
+    /// ### Errors
+    /// - When the [`Stream`] is an OTF stream, since those cannot be requested by byte ranges.
+    /// - When the server does not respond with partial content on resume.
+    pub async fn download_to_pausable<P: AsRef<Path>>(&self, path: P, handle: PauseHandle) -> Result<u64> {
+        log::trace!("download_to_pausable: {:?}", path.as_ref());
+        if self.is_otf {
+            return Err(Error::Custom("OTF streams cannot be downloaded in a pausable way".into()));
+        }
+        self.internal_download_body_to(path, DownloadOptions::new(), DownloadBody::Pausable(handle)).await
+    }
+
+    /// Attempts to downloads the [`Stream`]s resource, but only if it was modified after `since`,
+    /// i.e. the [`last_modified`](Stream::last_modified) of a previously downloaded [`Stream`].
+    /// This will download the video to the provided file path.
+    ///
+    /// If the [`last_modified`](Stream::last_modified) of this [`Stream`] is not newer than
+    /// `since`, no request is sent at all. Otherwise, the resource is requested with an
+    /// `If-Modified-Since` header, and only downloaded, if the server did not respond with
+    /// `304 Not Modified`.
+    pub async fn download_to_if_modified_since<P: AsRef<Path>>(
+        &self,
+        path: P,
+        since: DateTime<Utc>,
+    ) -> Result<DownloadOutcome> {
+        if self.last_modified <= since {
+            log::debug!("{} was not modified since {}", self.video_details.video_id, since);
+            return Ok(DownloadOutcome::NotModified);
+        }
+
+        let res = self
+            .send(|client| client
+                .get(self.signature_cipher.url.as_str())
+                .header(
+                    reqwest::header::IF_MODIFIED_SINCE,
+                    since.format("%a, %d %b %Y %H:%M:%S GMT").to_string(),
+                )
+            )
+            .await?;
+        if res.status() == reqwest::StatusCode::NOT_MODIFIED {
+            log::debug!("{} was not modified since {} according to the server", self.video_details.video_id, since);
+            return Ok(DownloadOutcome::NotModified);
+        }
+        let res = self.error_for_status(res)?;
+
+        self.internal_download_body_to(path, DownloadOptions::new(), DownloadBody::Response(res))
+            .await
+            .map(DownloadOutcome::Downloaded)
+    }
+
+    /// Attempts to download only the provided byte `range` of the [`Stream`]s resource.
+    /// This will download the range to the provided file path, using a single `Range` request.
+    ///
+    /// ### Errors
+    /// - When the range is empty, or exceeds the content length of the [`Stream`].
+    /// - When the server does not respond with partial content.
+    pub async fn download_range_to<P: AsRef<Path>>(&self, path: P, range: Range<u64>) -> Result<u64> {
+        self.download_range_with(path, range, DownloadOptions::new()).await
+    }
+
+    /// Attempts to download only the first `max_segments` segments of a sequenced (i.e. OTF)
+    /// [`Stream`] to the provided file path, i.e. to extract a thumbnail from the first seconds of
+    /// a video.
+    ///
+    /// The header segment, which is required to play the file, is always downloaded, and does
+    /// not count towards `max_segments`. If the [`Stream`] has less segments, all of them are
+    /// downloaded.
+    ///
+    /// ### Errors
+    /// - When the [`Stream`] cannot be downloaded using sequence numbers.
+    pub async fn download_segments_to<P: AsRef<Path>>(&self, path: P, max_segments: u64) -> Result<u64> {
+        log::trace!("download_segments_to: {:?} ({} segments)", path.as_ref(), max_segments);
+        self.internal_download_body_to(path, DownloadOptions::new(), DownloadBody::Segments(max_segments)).await
+    }
+
+    /// Attempts to download a sequenced (i.e. OTF) [`Stream`] to the provided file path, and
+    /// resumes a previous, interrupted call to this method with the same path.
+    ///
+    /// After every segment, the index of the segment is persisted in a manifest next to the file
+    /// (`<path>.seq`). If both the file and the manifest exist when this method is called, the
+    /// file is truncated to the end of the last recorded segment, and the download continues with
+    /// the next segment, instead of starting over. Once the download finished, the manifest is
+    /// removed.
+    ///
+    /// Unlike the other download methods, the partially downloaded file is **not** removed, if
+    /// the download fails, so it can be resumed later on.
+    ///
+    /// ### Errors
+    /// - When the [`Stream`] cannot be downloaded using sequence numbers.
+    pub async fn download_seq_resumable_to<P: AsRef<Path>>(&self, path: P) -> Result<u64> {
+        log::trace!("download_seq_resumable_to: {:?}", path.as_ref());
+        self.resume_seq_to(path.as_ref(), DownloadOptions::new()).await
+    }
+
+    /// Attempts to downloads the [`Stream`]s resource.
+    /// This will write the video to the provided writer, instead of a file.
+    ///
+    /// Since there's no file that could be removed, the writer may already contain parts of the
+    /// video, when the download fails.
+    #[inline]
+    pub async fn download_to_writer<W: AsyncWrite + Unpin + ?Sized>(&self, writer: &mut W) -> Result<u64> {
+        log::trace!("download_to_writer");
+        let bytes = self.internal_download_to_writer(writer, &None).await?;
+        log::info!("downloaded {} successfully to a writer", self.video_details.video_id);
+        Ok(bytes)
+    }
+
+    /// Attempts to downloads the [`Stream`]s resource.
+    /// This will write the video to the standard output, i.e. to pipe it into another program
+    /// (`... | ffmpeg -i - ...`).
+    ///
+    /// The output is buffered, and flushed once the download is done. Since there's no file that
+    /// could be removed, parts of the video may already have been written, when the download
+    /// fails. The standard output is not locked during the download, so nothing else should
+    /// write to it in the meantime (i.e. log to stderr instead).
+    #[inline]
+    pub async fn download_to_stdout(&self) -> Result<u64> {
+        log::trace!("download_to_stdout");
+        let mut stdout = BufWriter::with_capacity(DEFAULT_WRITE_BUFFER_SIZE, tokio::io::stdout());
+        let bytes = self.internal_download_to_writer(&mut stdout, &None).await?;
+        log::info!("downloaded {} successfully to stdout", self.video_details.video_id);
+        Ok(bytes)
+    }
+
+    /// Attempts to download the [`Stream`]s resource, and sends the downloaded chunks through a
+    /// channel, that can buffer up to `capacity` chunks.
+    ///
+    /// The download runs in a [`tokio::spawn`]ed task, and pauses while the channel is full, so it
+    /// never gets ahead of the receiver. If the download fails, the error is sent as the last
+    /// item. Dropping the receiver stops the download.
+    ///
+    /// ### Panics
+    /// When called outside of a [`tokio`] runtime.
+    pub fn download_chunks(&self, capacity: usize) -> tokio::sync::mpsc::Receiver<Result<bytes::Bytes>> {
+        let (sender, receiver) = tokio::sync::mpsc::channel(capacity.max(1));
+        let stream = self.clone();
+
+        tokio::spawn(async move {
+            match stream.send_chunks(&sender).await {
+                Ok(bytes) => log::info!("downloaded {} successfully ({} bytes) to a channel", stream.video_details.video_id, bytes),
+                Err(e) if sender.is_closed() => {
+                    log::debug!("stopped downloading {}, since the receiver was dropped: {:?}", stream.video_details.video_id, e);
+                }
+                Err(e) => {
+                    log::error!("failed to download {} to a channel: {:?}", stream.video_details.video_id, e);
+                    let _ = sender.send(Err(e)).await;
+                }
+            }
+        });
+
+        receiver
+    }
+
+    /// Attempts to download the [`Stream`]s resource into memory.
+    ///
+    /// Downloads exceeding [`DEFAULT_MAX_BYTES`] fail with [`Error::TooLarge`], use
+    /// [`Stream::download_bytes_with_limit`] for a different limit.
+    #[inline]
+    pub async fn download_bytes(&self) -> Result<Vec<u8>> {
+        self.download_bytes_with_limit(DEFAULT_MAX_BYTES).await
+    }
+
+    /// Attempts to download the [`Stream`]s resource into memory.
+    ///
+    /// ### Errors
+    /// - [`Error::TooLarge`], when the [`Stream`] is larger than `max_size` bytes. If the content
+    /// length is already known, no request is sent in this case.
+    /// - When the download fails.
+    pub async fn download_bytes_with_limit(&self, max_size: u64) -> Result<Vec<u8>> {
+        log::trace!("download_bytes_with_limit: {}", max_size);
+        let content_length = self.content_length.get().copied();
+        if content_length.map_or(false, |cl| cl > max_size) {
+            return Err(Error::TooLarge { max_size });
+        }
+
+        let capacity = content_length.map_or(0, |cl| std::convert::TryFrom::try_from(cl).unwrap_or(usize::MAX));
+        let mut buffer = limited::Limited::new(Vec::with_capacity(capacity), Some(max_size));
+
+        match self.internal_download_to_writer(&mut buffer, &None).await {
+            Ok(bytes) => {
+                log::info!("downloaded {} successfully ({} bytes) into memory", self.video_details.video_id, bytes);
+                Ok(buffer.into_inner())
+            }
+            Err(_) if buffer.exceeded() => Err(Error::TooLarge { max_size }),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Downloads the [thumbnail](Stream::thumbnail_url) of the video the [`Stream`] belongs to
+    /// into memory, i.e. to show it next to the download.
+    ///
+    /// ### Errors
+    /// - When the video has no thumbnail.
+    /// - When the request fails, or YouTube responds with an unsuccessful status code.
+    pub async fn download_thumbnail(&self) -> Result<Vec<u8>> {
+        let url = self
+            .thumbnail_url()
+            .ok_or_else(|| Error::UnexpectedResponse("the video has no valid thumbnail".into()))?;
+        log::trace!("download_thumbnail: {}", url.as_str());
+        let bytes = self
+            .send(|client| client.get(url.as_str()))
+            .await?
+            .error_for_status()?
+            .bytes()
+            .await?;
+        Ok(bytes.to_vec())
+    }
+
+    /// Attempts to downloads the [`Stream`]s resource, as configured by the [`DownloadOptions`].
+    /// Returns the path the [`Stream`] was downloaded to.
+    ///
+    /// Failed attempts are retried, if the `options` allow so. Every attempt starts from scratch,
+    /// unless the download is [resumed](OverwritePolicy::Resume).
+    pub async fn download_with(&self, options: DownloadOptions) -> Result<PathBuf> {
+        let suggested_file_name = match options.destination {
+            download_options::Destination::Path(_) => None,
+            _ if options.content_disposition => self.head_info().await?.suggested_file_name,
+            _ => None,
+        };
+        let file_name = || match suggested_file_name {
+            Some(ref file_name) => PathBuf::from(file_name),
+            None => self.default_file_name(options.unique_file_name),
+        };
+        let path = match options.destination {
+            download_options::Destination::Default => file_name(),
+            download_options::Destination::Dir(ref dir) => dir.join(file_name()),
+            download_options::Destination::Path(ref path) => path.clone(),
+        };
+
+        let exists = tokio::fs::metadata(&path).await.is_ok();
+        match options.overwrite {
+            download_options::OverwritePolicy::Skip if exists => {
+                log::info!("skipped the download of {}, {:?} already exists", self.video_details.video_id, path);
+                return Ok(path);
+            }
+            download_options::OverwritePolicy::Error if exists => return Err(Error::FileExists(path)),
+            _ => {}
+        }
+        let resume = options.overwrite == download_options::OverwritePolicy::Resume;
+
+        let mut stream = match (&options.client, options.max_redirects) {
+            (Some(_), Some(_)) => return Err(Error::Custom(
+                "`max_redirects` cannot be combined with a custom `client`, \
+                configure the redirect policy when building the client instead".into()
+            )),
+            (Some(client), None) => self.clone_with_client(client.clone()),
+            (None, Some(max_redirects)) => self.clone_with_max_redirects(max_redirects)?,
+            (None, None) => self.clone(),
+        };
+        if let Some(ref headers) = options.headers {
+            stream = stream.with_headers(headers.clone());
+        }
+        if let Some(read_buffer_size) = options.read_buffer_size {
+            stream = stream.with_read_buffer_size(read_buffer_size);
+        }
+        if let Some(sequenced_window) = options.sequenced_window {
+            stream = stream.with_sequenced_window(sequenced_window);
+        }
+
+        let mut attempt = 0;
+        let mut integrity_attempt = 0;
+        loop {
+            let result = match options.range {
+                Some(ref range) => stream.download_range_with(&path, range.clone(), options.clone()).await,
+                None if resume => stream.resume_to(&path, options.clone()).await,
+                None => stream.internal_download_to_options(&path, options.clone()).await,
+            };
+
+            if let Err(Error::Incomplete { expected, actual }) = result {
+                if integrity_attempt < options.integrity_retries {
+                    integrity_attempt += 1;
+                    log::warn!(
+                        "download of {} is incomplete ({}/{} bytes), redownloading it ({}/{})",
+                        self.video_details.video_id, actual, expected, integrity_attempt, options.integrity_retries
+                    );
+                    stream.observe_retry(attempt + integrity_attempt, &Error::Incomplete { expected, actual });
+                    continue;
+                }
+            }
+
+            let retry_after = match result {
+                Err(Error::RateLimited { retry_after }) => Some(retry_after),
+                Err(ref e) if is_transient(e) => Some(None),
+                _ => None,
+            };
+            match retry_after {
+                Some(retry_after) if attempt < options.retries => {
+                    attempt += 1;
+                    let delay = retry_after.unwrap_or(options.retry_delay);
+                    if options.deadline.map_or(false, |deadline| {
+                        std::time::Instant::now() + delay >= deadline
+                    }) {
+                        log::error!(
+                            "download of {} failed, and cannot be retried before its deadline",
+                            self.video_details.video_id
+                        );
+                        return Err(Error::DeadlineExceeded);
+                    }
+                    log::warn!(
+                        "download of {} failed, retrying in {:?} ({}/{})",
+                        self.video_details.video_id, delay, attempt, options.retries
+                    );
+                    if let Err(ref e) = result {
+                        stream.observe_retry(attempt + integrity_attempt, e);
+                    }
+                    tokio::time::sleep(delay).await;
+                }
+                _ => {
+                    result?;
+                    break;
+                }
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        if options.sidecar {
+            self.write_sidecar(&path).await?;
+        }
+
+        Ok(path)
+    }
+
+    /// Clones the [`Stream`], but rebuilds its [`Client`], so it follows at most `max_redirects`
+    /// redirects, while keeping its connection pool settings and its cookie jar.
+    ///
+    /// ### Errors
+    /// - When the [`Stream`] uses a [`ProxyPool`], or a [`Client`], that wasn't created by a
+    /// [`VideoFetcher`](crate::VideoFetcher), since their configuration is unknown.
+    /// - When [`reqwest`] fails to initialize the new [`Client`].
+    fn clone_with_max_redirects(&self, max_redirects: usize) -> Result<Self> {
+        if self.proxy_pool.is_some() {
+            return Err(Error::Custom(
+                "`max_redirects` cannot be combined with a ProxyPool, \
+                configure the redirect policy when building its clients instead".into()
+            ));
+        }
+        let client_config = self.client_config.as_ref().ok_or_else(|| Error::Custom(
+            "`max_redirects` requires a client created by a VideoFetcher, \
+            configure the redirect policy when building custom clients instead".into()
+        ))?;
+
+        let policy = match max_redirects {
+            0 => reqwest::redirect::Policy::none(),
+            max_redirects => reqwest::redirect::Policy::limited(max_redirects),
+        };
+        Ok(Self {
+            client: client_config.build(policy)?,
+            ..self.clone()
+        })
+    }
+
+    /// Continues a partial download to `path`, or starts it, if there's no file to continue yet
+    /// (see [`OverwritePolicy::Resume`]).
+    async fn resume_to(&self, path: &Path, options: DownloadOptions) -> Result<u64> {
+        if self.is_otf {
+            return self.resume_seq_to(path, options).await;
+        }
+
+        let content_length = self.content_length().await?;
+        let offset = tokio::fs::metadata(path).await.map_or(0, |metadata| metadata.len());
+        if offset == content_length && offset > 0 {
+            log::info!("{:?} already contains all of {}", path, self.video_details.video_id);
+            return Ok(offset);
+        }
+        if offset > content_length {
+            // the file cannot be a partial download of this stream
+            return self.internal_download_to_options(path, options).await;
+        }
+
+        log::debug!("resuming the download of {} at {} bytes", self.video_details.video_id, offset);
+        let file = tokio::fs::OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(path)
+            .await?;
+        let body = match offset {
+            0 => DownloadBody::Full,
+            offset => DownloadBody::From(offset),
+        };
+        let bytes = self.resume_download(path, file, body, options).await?;
+        log::info!("resumed the download of {} successfully to {:?}", self.video_details.video_id, path);
+        Ok(bytes)
+    }
+
+    /// Continues a sequenced download to `path` after its last recorded segment, or starts it, if
+    /// there's no previous download to continue.
+    async fn resume_seq_to(&self, path: &Path, options: DownloadOptions) -> Result<u64> {
+        let previous = match tokio::fs::metadata(path).await {
+            Ok(_) => segment_progress::SegmentProgress::load(path).await,
+            Err(_) => None,
+        };
+        let (file, mut progress) = match previous {
+            Some(progress) => {
+                let mut file = tokio::fs::OpenOptions::new()
+                    .write(true)
+                    .open(path)
+                    .await?;
+                // Drop everything written after the last recorded segment
+                file.set_len(progress.bytes()).await?;
+                tokio::io::AsyncSeekExt::seek(&mut file, std::io::SeekFrom::End(0)).await?;
+                (file, progress)
+            }
+            None => (File::create(path).await?, segment_progress::SegmentProgress::new(path)),
+        };
+
+        match self.resume_download(path, file, DownloadBody::Sequenced(&mut progress), options).await {
+            Ok(bytes) => {
+                progress.finish().await?;
+                log::info!("downloaded {} successfully to {:?}", self.video_details.video_id, path);
+                Ok(bytes)
+            }
+            Err(e) => {
+                log::error!(
+                    "failed to download {}, it can be resumed from segment {}: {:?}",
+                    self.video_details.video_id, progress.next_segment(), e
+                );
+                Err(e)
+            }
+        }
+    }
+
+    /// Downloads `body` to the partially downloaded `file` at `path`, as configured by the
+    /// `options`. Unlike [`download_via_part_file`](Stream::download_via_part_file), the file is
+    /// kept, if the download fails, so it can be resumed later on.
+    #[allow(unused_mut)]
+    async fn resume_download(
+        &self,
+        path: &Path,
+        file: File,
+        body: DownloadBody<'_>,
+        mut options: DownloadOptions,
+    ) -> Result<u64> {
+        let start = std::time::Instant::now();
+        self.check_max_bytes(&body, options.max_bytes)?;
+        let mut file = BufWriter::with_capacity(options.write_buffer_size, file);
+        let result = self
+            .write_download(&mut file, body, &mut options)
+            .await
+            .map_err(|e| with_write_path(e, path));
+        drop(file);
+
+        #[cfg(feature = "callback")]
+        Self::finish_callback(&mut options.callback, &result, path).await;
+        self.observe_finish(&result, start);
+        result
+    }
+
+    /// The file name a [`Stream`] is downloaded to, if no path is specified:
+    /// `<video_id>.f<itag>.<extension>`.
+    /// If `unique` is set, the process id and the current time are added as well
+    /// (`<video_id>.f<itag>.<pid>-<nanos>.<extension>`), so no two downloads share a file name.
+    fn default_file_name(&self, unique: bool) -> PathBuf {
+        let mut file_name = format!("{}.f{}", self.video_details.video_id, self.itag);
+        if unique {
+            let nanos = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map_or(0, |since_epoch| since_epoch.as_nanos());
+            file_name.push_str(&format!(".{}-{}", std::process::id(), nanos));
+        }
+        file_name.push('.');
+        file_name.push_str(self.file_extension());
+        PathBuf::from(file_name)
+    }
+
+    #[inline]
+    async fn internal_download_to<P: AsRef<Path>>(
+        &self,
+        path: P,
+        callback: Option<Callback>,
+        timeout: Option<Duration>,
+        cancel: Option<CancellationToken>,
+    ) -> Result<u64> {
+        let options = DownloadOptions {
+            callback,
+            timeout,
+            cancel,
+            ..DownloadOptions::new()
+        };
+        self.internal_download_to_options(path, options).await
+    }
+
+    async fn internal_download_to_options<P: AsRef<Path>>(&self, path: P, options: DownloadOptions) -> Result<u64> {
+        self.internal_download_body_to(path, options, DownloadBody::Full).await
+    }
+
+    /// Downloads the byte `range` to `path`, as configured by the `options`.
+    async fn download_range_with<P: AsRef<Path>>(
+        &self,
+        path: P,
+        range: Range<u64>,
+        options: DownloadOptions,
+    ) -> Result<u64> {
+        log::trace!("download_range_to: {:?} ({:?})", path.as_ref(), range);
+        if range.start >= range.end {
+            return Err(Error::InvalidRange(range));
+        }
+        if let Ok(content_length) = self.content_length().await {
+            if range.end > content_length {
+                return Err(Error::InvalidRange(range));
+            }
+        }
+        self.internal_download_body_to(path, options, DownloadBody::Range(range)).await
+    }
+
+    async fn internal_download_body_to<P: AsRef<Path>>(
+        &self,
+        path: P,
+        options: DownloadOptions,
+        body: DownloadBody<'_>,
+    ) -> Result<u64> {
+        let start = std::time::Instant::now();
+        // Preallocating the file lets the file system allocate it contiguously. OTF streams have
+        // no known size, and are therefore never preallocated.
+        let preallocate = self.expected_len(&body);
+        let open = |part_path: PathBuf| async move {
+            let file = File::create(&part_path).await?;
+            if let Some(len) = preallocate {
+                file.set_len(len).await?;
+            }
+            Ok(file)
+        };
+        let result = self.download_via_part_file(path, options, body, open).await;
+        self.observe_finish(&result, start);
+        result
     }
 
+    /// Downloads `body` to the `<path>.<pid>.part` file, which is opened using `open`, and
+    /// renamed to `path` once the download succeeded, or removed, if it failed.
     #[allow(unused_mut)]
-    async fn internal_download_to<P: AsRef<Path>>(&self, path: P, mut callback: Option<Callback>) -> Result<()> {
+    async fn download_via_part_file<P, W, F, Fut>(
+        &self,
+        path: P,
+        mut options: DownloadOptions,
+        body: DownloadBody<'_>,
+        open: F,
+    ) -> Result<u64>
+        where
+            P: AsRef<Path>,
+            W: AsyncWrite + Unpin,
+            F: FnOnce(PathBuf) -> Fut,
+            Fut: Future<Output=Result<W>> {
         log::trace!("download_to: {:?}", path.as_ref());
-        let mut file = File::create(&path).await?;
+        self.check_max_bytes(&body, options.max_bytes)?;
+
+        // Downloading to a temporary file first ensures, that there's never a half written file at
+        // `path`, even if the process crashes mid-download.
+        let part_path = match options.temp_dir {
+            Some(ref temp_dir) => temp_dir.join(part_path(path.as_ref()).file_name().unwrap_or_default()),
+            None => part_path(path.as_ref()),
+        };
+        let file = open(part_path.clone()).await?;
+        // Chunks, especially the ones of OTF streams, can be tiny, so they are buffered to reduce
+        // the number of syscalls.
+        let mut file = BufWriter::with_capacity(options.write_buffer_size, file);
+        let result = self.write_download(&mut file, body, &mut options).await;
+        drop(file);
+
+        let result = match result {
+            Ok(bytes) => match move_file(&part_path, path.as_ref()).await {
+                Ok(()) => {
+                    log::info!(
+                        "downloaded {} successfully to {:?}",
+                        self.video_details.video_id, path.as_ref()
+                    );
+                    log::debug!("downloaded stream {:?}", &self);
+                    Ok(bytes)
+                }
+                Err(e) => {
+                    remove_part_file(&part_path).await;
+                    Err(Error::IO(e))
+                }
+            }
+            Err(e) => {
+                remove_part_file(&part_path).await;
+                Err(with_write_path(e, &part_path))
+            }
+        };
 
-        // fixme: Requires 'static
         #[cfg(feature = "callback")]
-        let handle = if let Some(ref mut callback) = callback {
+        Self::finish_callback(&mut options.callback, &result, path.as_ref()).await;
+        result
+    }
 
-            Some(task::spawn_local(Self::on_progress(
+    /// Fails with [`Error::SizeLimitExceeded`], if the file would exceed `max_bytes` after
+    /// downloading `body`, so nothing is downloaded in vain.
+    fn check_max_bytes(&self, body: &DownloadBody<'_>, max_bytes: Option<u64>) -> Result<()> {
+        match (max_bytes, self.expected_len(body)) {
+            (Some(max_bytes), Some(len)) if len > max_bytes => Err(Error::SizeLimitExceeded { limit: max_bytes }),
+            _ => Ok(()),
+        }
+    }
+
+    /// The size of the file, once `body` is downloaded, if it's known upfront.
+    fn expected_len(&self, body: &DownloadBody<'_>) -> Option<u64> {
+        match body {
+            DownloadBody::Range(range) => Some(range.end - range.start),
+            DownloadBody::Segments(_) | DownloadBody::Sequenced(_) => None,
+            _ if self.is_otf => None,
+            _ => self.content_length.get().copied(),
+        }
+    }
+
+    /// Downloads `body` to the `writer`, as configured by the `options`, and shuts the `writer`
+    /// down afterwards.
+    ///
+    /// The `options` report the progress to their callback, throttle and limit the download, and
+    /// abort it, if it times out, passes its deadline, or gets cancelled.
+    #[allow(unused_mut)]
+    async fn write_download<W: AsyncWrite + Unpin>(
+        &self,
+        writer: &mut W,
+        body: DownloadBody<'_>,
+        options: &mut DownloadOptions,
+    ) -> Result<u64> {
+        let expected = self.expected_len(&body);
+        let DownloadOptions {
+            ref mut callback, timeout, deadline, ref cancel, bytes_per_sec, max_bytes, ..
+        } = *options;
+
+        #[cfg(feature = "callback")]
+        let progress = if let Some(ref mut callback) = callback {
+            let total_bytes = match body {
+                DownloadBody::Range(_) => expected,
+                _ => self.total_bytes().await,
+            };
+            Self::on_start(std::mem::take(&mut callback.on_start), total_bytes).await;
+            Some(Self::on_progress(
                 callback.internal_receiver.take().expect("Callback cannot be used twice"),
-                std::mem::take(&mut callback.on_progress)
-            )))
+                std::mem::take(&mut callback.on_progress),
+                total_bytes,
+                callback.progress_interval,
+            ))
         } else {
             None
         };
-
-        let result = match self.download_full(&self.signature_cipher.url, &mut file, &callback, 0).await {
-            Ok(_) => {
-                log::info!(
-                    "downloaded {} successfully to {:?}",
-                    self.video_details.video_id, path.as_ref()
-                );
-                log::debug!("downloaded stream {:?}", &self);
-                Ok(())
+
+        let download = async {
+            // The limit applies to the whole file, including the bytes of a previous download,
+            // that is resumed.
+            let limit = max_bytes.map(|max_bytes| max_bytes.saturating_sub(body.offset()));
+            let mut writer = limited::Limited::new(throttle::Throttled::new(&mut *writer, bytes_per_sec), limit);
+            let result = Self::limit_download(
+                self.write_body(body, &mut writer, callback),
+                timeout,
+                deadline,
+                cancel.clone(),
+            ).await;
+            let result = match (result, max_bytes) {
+                (Err(_), Some(max_bytes)) if writer.exceeded() => {
+                    log::error!("download of {} exceeded {} bytes", self.video_details.video_id, max_bytes);
+                    Err(Error::SizeLimitExceeded { limit: max_bytes })
+                }
+                (result, _) => result,
+            };
+            // An empty file is useless, unless the stream is known to be empty.
+            let result = match result {
+                Ok(0) if expected != Some(0) => {
+                    log::error!("download of {} is empty", self.video_details.video_id);
+                    Err(Error::EmptyStream)
+                }
+                result => result,
+            };
+            // Sometimes the CDN silently truncates a response, so a download, that is shorter than
+            // the expected size, is not considered successful.
+            let result = match (result, expected) {
+                (Ok(bytes), Some(expected)) if bytes != expected => {
+                    log::error!(
+                        "download of {} is incomplete: expected {} bytes, got {}",
+                        self.video_details.video_id, expected, bytes
+                    );
+                    Err(Error::Incomplete { expected, actual: bytes })
+                }
+                (result, _) => result,
+            };
+            match result {
+                Err(Error::Timeout) => log::error!(
+                    "download of {} timed out after {:?}",
+                    self.video_details.video_id, timeout
+                ),
+                Err(Error::Cancelled) => log::info!("download of {} was cancelled", self.video_details.video_id),
+                Err(Error::DeadlineExceeded) => log::error!(
+                    "download of {} did not finish before its deadline",
+                    self.video_details.video_id
+                ),
+                _ => {}
+            }
+
+            // dropping the last sender closes the channel, which lets the progress loop finish
+            #[cfg(feature = "callback")]
+            if let Some(sender) = callback.as_mut().and_then(|c| c.internal_sender.take()) {
+                // Regular progress updates are dropped when the channel is full, so the final
+                // count is sent separately, to guarantee the progress reaches 100%.
+                if let Ok(bytes) = result {
+                    let _ = sender.send(bytes).await;
+                }
+            }
+            result
+        };
+
+        #[cfg(feature = "callback")]
+        let result = match progress {
+            Some(progress) => tokio::join!(download, progress).0,
+            None => download.await,
+        };
+        #[cfg(not(feature = "callback"))]
+        let result = download.await;
+
+        // A preallocated file always has the expected size, since any other size fails the download
+        // as incomplete. Shutting the writer down lets wrapping writers finalize their output.
+        match result {
+            Ok(bytes) => writer.shutdown().await.map(|_| bytes).map_err(Error::from),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Requests `body`, and writes it to the `writer`.
+    async fn write_body<W: AsyncWrite + Unpin + ?Sized>(
+        &self,
+        body: DownloadBody<'_>,
+        writer: &mut W,
+        callback: &Option<Callback>,
+    ) -> Result<u64> {
+        match body {
+            DownloadBody::Full => self.internal_download_to_writer(writer, callback).await,
+            DownloadBody::Response(res) => self.write_stream_to_file(res.bytes_stream(), writer, callback, 0).await,
+            DownloadBody::Range(range) => {
+                let res = self.request_partial(format!("bytes={}-{}", range.start, range.end - 1)).await?;
+                self.write_stream_to_file(res.bytes_stream(), writer, callback, 0).await
+            }
+            DownloadBody::From(offset) => {
+                let res = self.request_partial(format!("bytes={}-", offset)).await?;
+                self.write_stream_to_file(res.bytes_stream(), writer, callback, offset).await
+            }
+            DownloadBody::Pausable(handle) => self.write_pausable(handle, writer, callback).await,
+            DownloadBody::Segments(max_segments) => {
+                self.download_full_seq(writer, callback, Some(max_segments), None).await
+            }
+            DownloadBody::Sequenced(progress) => {
+                self.download_full_seq(writer, callback, None, Some(progress)).await
+            }
+        }
+    }
+
+    /// Requests the whole resource, and writes it to the `writer`, until the `handle` gets paused.
+    /// Once it's resumed, the rest of the resource is requested using a `Range` request.
+    async fn write_pausable<W: AsyncWrite + Unpin + ?Sized>(
+        &self,
+        handle: PauseHandle,
+        writer: &mut W,
+        callback: &Option<Callback>,
+    ) -> Result<u64> {
+        let mut offset = 0;
+        loop {
+            handle.resumed().await;
+            let res = match offset {
+                0 => self
+                    .send(|client| client.get(self.signature_cipher.url.as_str()))
+                    .await
+                    .and_then(|res| self.error_for_status(res))?,
+                offset => self.request_partial(format!("bytes={}-", offset)).await?,
+            };
+
+            let mut chunks = res.bytes_stream();
+            let paused = loop {
+                tokio::select! {
+                    biased;
+                    _ = handle.paused() => break true,
+                    chunk = chunks.next() => match chunk {
+                        Some(chunk) => {
+                            offset = self
+                                .write_stream_to_file(tokio_stream::once(chunk), writer, callback, offset)
+                                .await?;
+                        }
+                        None => break false,
+                    },
+                }
+            };
+
+            if !paused { return Ok(offset); }
+            log::debug!("paused the download of {} at {} bytes", self.video_details.video_id, offset);
+        }
+    }
+
+    /// Requests the byte `range` of the [`Stream`]s resource, which the server has to respond to
+    /// with partial content.
+    async fn request_partial(&self, range: String) -> Result<reqwest::Response> {
+        let res = self
+            .send(|client| client
+                .get(self.signature_cipher.url.as_str())
+                .header(reqwest::header::RANGE, range.as_str())
+            )
+            .await
+            .and_then(|res| self.error_for_status(res))?;
+        if res.status() != reqwest::StatusCode::PARTIAL_CONTENT {
+            return Err(Error::UnexpectedResponse(
+                "the server did not respond with partial content".into()
+            ));
+        }
+        Ok(res)
+    }
+
+    /// Calls the `on_error` and `on_complete` callbacks, once the download to `path` finished.
+    #[cfg(feature = "callback")]
+    async fn finish_callback(callback: &mut Option<Callback>, result: &Result<u64>, path: &Path) {
+        if let Some(ref mut callback) = callback {
+            if let Err(e) = result {
+                Self::on_error(std::mem::take(&mut callback.on_error), e);
+            }
+            let path = result.as_ref().ok().map(|_| path.to_path_buf());
+            Self::on_complete(std::mem::take(&mut callback.on_complete), path).await;
+        }
+    }
+
+    #[inline]
+    fn observe_finish(&self, result: &Result<u64>, start: std::time::Instant) {
+        if let Some(ref observer) = self.observer {
+            observer.on_finish(self, result.as_ref().map(|&bytes| bytes), start.elapsed());
+        }
+    }
+
+    #[inline]
+    fn observe_retry(&self, attempt: usize, error: &Error) {
+        if let Some(ref observer) = self.observer {
+            observer.on_retry(self, attempt, error);
+        }
+    }
+
+    /// Aborts the download, when the `timeout` expires, when the `deadline` passes, or when the
+    /// `cancel` token gets cancelled.
+    async fn limit_download(
+        download: impl Future<Output=Result<u64>>,
+        timeout: Option<Duration>,
+        deadline: Option<std::time::Instant>,
+        cancel: Option<CancellationToken>,
+    ) -> Result<u64> {
+        let download = async {
+            match timeout {
+                Some(timeout) => tokio::time::timeout(timeout, download)
+                    .await
+                    .unwrap_or(Err(Error::Timeout)),
+                None => download.await,
+            }
+        };
+        let download = async {
+            match deadline {
+                Some(deadline) => tokio::time::timeout_at(deadline.into(), download)
+                    .await
+                    .unwrap_or(Err(Error::DeadlineExceeded)),
+                None => download.await,
             }
-            Err(Error::Request(e)) if e.status().contains(&reqwest::StatusCode::NOT_FOUND) => {
+        };
+
+        match cancel {
+            Some(token) => tokio::select! {
+                result = download => result,
+                _ = token.cancelled() => Err(Error::Cancelled),
+            },
+            None => download.await,
+        }
+    }
+
+    async fn internal_download_to_writer<W: AsyncWrite + Unpin + ?Sized>(
+        &self,
+        writer: &mut W,
+        callback: &Option<Callback>,
+    ) -> Result<u64> {
+        let bytes = match self.download_full(&self.signature_cipher.url, writer, callback, 0).await {
+            Ok(bytes) => Ok(bytes),
+            Err(Error::Request(e))
+            if self.sequenced_fallback && e.status().contains(&reqwest::StatusCode::NOT_FOUND) => {
                 log::error!("failed to download {}: {:?}", self.video_details.video_id, e);
                 log::info!("try to download {} using sequenced download", self.video_details.video_id);
                 // Some adaptive streams need to be requested with sequence numbers
-                self.download_full_seq(&mut file, &callback)
+                self.download_full_seq(writer, callback, None, None)
                     .await
                     .map_err(|e| {
                         log::error!(
@@ -277,42 +2336,98 @@ impl Stream {
             }
             Err(e) => {
                 log::error!("failed to download {}: {:?}", self.video_details.video_id, e);
-                drop(file);
-                tokio::fs::remove_file(path.as_ref()).await?;
                 Err(e)
             }
+        }?;
+
+        writer
+            .flush()
+            .await
+            .map_err(|e| write_error(e, bytes, None))?;
+        Ok(bytes)
+    }
+
+    /// Sends the chunks of the [`Stream`]s resource through the `sender`, as they are received.
+    /// While the channel is full, the download waits for the receiver.
+    async fn send_chunks(&self, sender: &tokio::sync::mpsc::Sender<Result<bytes::Bytes>>) -> Result<u64> {
+        let mut chunks = match self.get(&self.signature_cipher.url).await {
+            Ok(res) => futures::StreamExt::boxed(res.bytes_stream().map(|chunk| chunk.map_err(Error::from))),
+            Err(Error::Request(e))
+            if self.sequenced_fallback && e.status().contains(&reqwest::StatusCode::NOT_FOUND) => {
+                log::error!("failed to download {}: {:?}", self.video_details.video_id, e);
+                log::info!("try to download {} using sequenced download", self.video_details.video_id);
+                futures::StreamExt::boxed(
+                    self.seq_segments(0, None)
+                        .await?
+                        .map(|segment| segment.map(|(_, segment)| segment))
+                )
+            }
+            Err(e) => return Err(e),
         };
 
-        #[cfg(feature = "callback")]
-        {
-            if let Some(handle) = handle {
-                handle.abort();
-            }
-            let path = if let Ok(_) = &result {
-                let mut pathbuf = PathBuf::new();
-                pathbuf.push(path);
-                Some(pathbuf)
-            } else {
-                None
-            };
-            if let Some(ref mut callback) = callback {
-                Self::on_complete(std::mem::take(&mut callback.on_complete), path).await;
+        let mut count = 0;
+        while let Some(chunk) = chunks.next().await {
+            let chunk = chunk?;
+            let len = chunk.len();
+            sender.send(Ok(chunk)).await.map_err(|_| Error::IO(std::io::Error::new(
+                std::io::ErrorKind::BrokenPipe,
+                "the receiver of the chunks was dropped",
+            )))?;
+            count += len as u64;
+            if let Some(ref observer) = self.observer {
+                observer.on_bytes(self, len);
             }
         }
+        Ok(count)
+    }
+
+    /// Downloads a sequenced stream segment by segment.
+    /// If `max_segments` is `Some`, at most that many segments (not counting the header segment)
+    /// are downloaded.
+    ///
+    /// If `progress` is `Some`, the download starts at the next segment recorded in it, and every
+    /// segment is recorded after it was written (and the writer was flushed). When resuming, the
+    /// writer has to already contain the previously written segments.
+    async fn download_full_seq<W: AsyncWrite + Unpin + ?Sized>(
+        &self,
+        writer: &mut W,
+        callback: &Option<Callback>,
+        max_segments: Option<u64>,
+        mut progress: Option<&mut segment_progress::SegmentProgress>,
+    ) -> Result<u64> {
+        let resume_at = progress.as_ref().map_or(0, |progress| progress.next_segment());
+        let mut count = if resume_at > 0 {
+            log::debug!("resuming the sequenced download of {} at segment {}", self.video_details.video_id, resume_at);
+            // The header was already written by a previous download
+            progress.as_ref().map_or(0, |progress| progress.bytes())
+        } else {
+            0
+        };
 
+        let mut segments = self.seq_segments(resume_at, max_segments).await?;
+        while let Some(segment) = segments.next().await {
+            let (i, segment) = segment?;
+            let segment = tokio_stream::once(Ok(segment));
+            // every segment continues counting where the previous one stopped
+            count = self.write_stream_to_file(segment, writer, callback, count).await?;
+            Self::record_segment(writer, &mut progress, i, count).await?;
+        }
 
-        result
+        Ok(count)
     }
 
-    async fn download_full_seq(&self, file: &mut File, callback: &Option<Callback>) -> Result<()> {
-        // fixme: this implementation is **not** tested yet!
-        // To test it, I would need an url of a video, which does require sequenced downloading.
-        log::warn!(
-            "`download_full_seq` is not tested yet and probably broken!\n\
-            Please open a GitHub issue (https://github.com/DzenanJupic/rustube/issues) and paste \
-            the whole warning message in:\n\
-            id: {}\n\
-            url: {}",
+    /// The segments of a sequenced stream, together with their sequence numbers, in order.
+    ///
+    /// The header segment is always requested, since it tells how the file is segmented, but it's
+    /// only yielded, if `first_segment` is 0. If `max_segments` is `Some`, at most that many
+    /// segments (not counting the header segment) are yielded.
+    async fn seq_segments(
+        &self,
+        first_segment: u64,
+        max_segments: Option<u64>,
+    ) -> Result<futures::stream::BoxStream<'_, Result<(u64, bytes::Bytes)>>> {
+        log::debug!(
+            "download {} using sequenced download from {}",
             self.video_details.video_id,
             self.signature_cipher.url.as_str()
         );
@@ -327,65 +2442,295 @@ impl Stream {
         // information about how the file is segmented.
         Self::set_url_seq_query(&mut url, &base_query, 0);
         let res = self.get(&url).await?;
-        let segment_count = Stream::extract_segment_count(&res)?;
-        // No callback action since this is not really part of the progress
-        self.write_stream_to_file(res.bytes_stream(), file, &None, 0).await?;
-        let mut count = 0;
+        let headers = res.headers().clone();
+        let header_segment = res.bytes().await?;
+        let segment_count = Stream::extract_segment_count(&headers, &header_segment)?;
 
-        for i in 1..segment_count {
+        // The header is part of the file, unless it was already written by a previous download
+        let header = match first_segment {
+            0 => Some(Ok((0, header_segment))),
+            _ => None,
+        };
+        let first_segment = first_segment.max(1);
+        let segment_url = move |i| {
+            let mut url = url.clone();
             Self::set_url_seq_query(&mut url, &base_query, i);
-            count = self.download_full(&url, file, &callback, count).await?;
-        }
+            url
+        };
+
+        let segments = match segment_count {
+            Some(segment_count) => {
+                log::trace!("{} consists of {} segments", self.video_details.video_id, segment_count);
+                let segment_count = max_segments.map_or(segment_count, |max| segment_count.min(max));
+                // The segment count does not include the header segment
+                let segments = (first_segment..=segment_count).map(move |i| {
+                    let url = segment_url(i);
+                    async move { Ok::<_, Error>((i, self.get(&url).await?.bytes().await?)) }
+                });
+
+                // Up to `sequenced_window` segments are requested concurrently (which the client
+                // multiplexes over a single connection, if the server speaks HTTP/2), but they are
+                // still yielded in order. Since `buffered` yields the segments in the order of
+                // their sequence numbers, at most `sequenced_window` segments are held in memory.
+                futures::StreamExt::boxed(futures::StreamExt::buffered(
+                    futures::stream::iter(segments),
+                    self.sequenced_window,
+                ))
+            }
+            None => {
+                log::debug!(
+                    "{} does not advertise a Segment-Count, requesting segments until there are no more",
+                    self.video_details.video_id
+                );
+                let last_segment = max_segments.unwrap_or(u64::MAX);
+                // Some servers signal the end of the stream with an empty response or a 404,
+                // instead of telling us the number of segments upfront.
+                futures::StreamExt::boxed(futures::stream::try_unfold(first_segment, move |i| {
+                    let url = segment_url(i);
+                    async move {
+                        if i > last_segment {
+                            return Ok(None);
+                        }
+                        match self.get(&url).await {
+                            Ok(res) => {
+                                let segment = res.bytes().await?;
+                                Ok((!segment.is_empty()).then(|| ((i, segment), i + 1)))
+                            }
+                            Err(Error::Request(e)) if e.status().contains(&reqwest::StatusCode::NOT_FOUND) => Ok(None),
+                            Err(e) => Err(e),
+                        }
+                    }
+                }))
+            }
+        };
+
+        Ok(futures::StreamExt::boxed(futures::StreamExt::chain(futures::stream::iter(header), segments)))
+    }
 
+    /// Flushes `writer` and records `segment` in `progress`, if there is one.
+    #[inline]
+    async fn record_segment<W: AsyncWrite + Unpin + ?Sized>(
+        writer: &mut W,
+        progress: &mut Option<&mut segment_progress::SegmentProgress>,
+        segment: u64,
+        count: u64,
+    ) -> Result<()> {
+        if let Some(progress) = progress {
+            writer.flush().await?;
+            progress.record(segment, count).await?;
+        }
         Ok(())
     }
 
     #[inline]
-    async fn download_full(
+    async fn download_full<W: AsyncWrite + Unpin + ?Sized>(
         &self,
         url: &url::Url,
-        file: &mut File,
+        writer: &mut W,
         callback: &Option<Callback>,
-        count: usize,
-    ) -> Result<usize> {
+        count: u64,
+    ) -> Result<u64> {
         let res = self.get(url).await?;
-        self.write_stream_to_file(res.bytes_stream(), file, &callback, count).await
+        self.write_stream_to_file(res.bytes_stream(), writer, &callback, count).await
+    }
+
+    /// The total size of the download, as reported to a [`Callback`].
+    /// OTF streams, as well as streams, whose content length cannot be determined, have no known
+    /// size.
+    #[cfg(feature = "callback")]
+    #[inline]
+    async fn total_bytes(&self) -> Option<u64> {
+        if self.is_otf { return None; }
+        self.content_length().await.ok()
+    }
+
+    /// Sends the request built by `request`, either using the [`Stream`]s [`Client`], or through
+    /// the [`ProxyPool`], if there is one.
+    /// Requests, whose proxy could not be reached or timed out, are retried through the next
+    /// proxy of the pool, until every proxy was tried once.
+    async fn send(&self, request: impl Fn(&Client) -> reqwest::RequestBuilder) -> Result<reqwest::Response> {
+        let request = |client: &Client| {
+            // Media is requested uncompressed, so nothing but the raw resource is written to disk,
+            // even if the client doesn't decompress responses automatically.
+            let mut request = request(client).header(reqwest::header::ACCEPT_ENCODING, "identity");
+            if let Some(ref headers) = self.headers {
+                request = request.headers((**headers).clone());
+            }
+            match self.user_agent {
+                Some(ref user_agent) => request.header(reqwest::header::USER_AGENT, &**user_agent),
+                None => request,
+            }
+        };
+
+        let send = |client: &Client| {
+            let request = request(client).build();
+            let client = client.clone();
+            async move {
+                let request = request?;
+                if let Some(ref observer) = self.observer {
+                    observer.on_request_start(self, &request);
+                }
+                client.execute(request).await
+            }
+        };
+
+        let proxy_pool = match self.proxy_pool {
+            Some(ref proxy_pool) => proxy_pool,
+            None => return send(&self.client).await.map_err(request_error),
+        };
+
+        let mut attempts_left = proxy_pool.len();
+        loop {
+            attempts_left -= 1;
+            match send(proxy_pool.next_client()).await {
+                Err(e) if attempts_left > 0 && (e.is_connect() || e.is_timeout()) => {
+                    log::warn!("request through proxy failed, retrying through the next one: {}", e);
+                }
+                res => return res.map_err(request_error),
+            }
+        }
     }
 
     #[inline]
+    async fn head(&self) -> Result<reqwest::Response> {
+        log::trace!("head: {}", self.signature_cipher.url.as_str());
+        self.send(|client| client.head(self.signature_cipher.url.as_str())).await
+    }
+
     async fn get(&self, url: &url::Url) -> Result<reqwest::Response> {
         log::trace!("get: {}", url.as_str());
-        Ok(
-            self.client
-                .get(url.as_str())
-                .send()
-                .await?
-                .error_for_status()?
+        let mut result = self.get_once(url).await;
+        for fallback_url in self.fallback_urls() {
+            match result {
+                Err(ref e) if is_transient(e) => {}
+                _ => break,
+            }
+
+            let mut fallback_url = fallback_url.clone();
+            if let Some((_, sq)) = url.query_pairs().find(|(key, _)| key == "sq") {
+                fallback_url.query_pairs_mut().append_pair("sq", &sq);
+            }
+            log::warn!(
+                "request to {} failed, retrying using the fallback url {}",
+                url.as_str(), fallback_url.as_str()
+            );
+            result = self.get_once(&fallback_url).await;
+        }
+        result
+    }
+
+    #[inline]
+    async fn get_once(&self, url: &url::Url) -> Result<reqwest::Response> {
+        self.error_for_status(
+            self.send(|client| client.get(url.as_str())).await?
         )
     }
 
+    /// Like [`reqwest::Response::error_for_status`], but turns `429 Too Many Requests` responses
+    /// into [`Error::RateLimited`], so callers know when to back off.
+    /// `403 Forbidden` responses to deciphered URLs are turned into [`Error::Cipher`], since
+    /// they're usually caused by an outdated cipher.
+    /// Responses, that are still compressed, are turned into [`Error::UnexpectedContentEncoding`],
+    /// since their body is not the raw resource.
+    fn error_for_status(&self, res: reqwest::Response) -> Result<reqwest::Response> {
+        // Redirects are followed by the client, so only redirects, which exceed its redirect policy,
+        // end up here. Other 3xx responses, i.e. `304 Not Modified`, don't redirect anywhere.
+        let location = res.headers().get(reqwest::header::LOCATION);
+        if let (true, Some(location)) = (res.status().is_redirection(), location) {
+            log::error!("{} was redirected unexpectedly to {:?}", self.video_details.video_id, location);
+            return Err(Error::TooManyRedirects(Some(res.url().clone())));
+        }
+
+        let content_encoding = res
+            .headers()
+            .get(reqwest::header::CONTENT_ENCODING)
+            .map(|ce| String::from_utf8_lossy(ce.as_bytes()).trim().to_owned())
+            .filter(|ce| !ce.is_empty() && !ce.eq_ignore_ascii_case("identity"));
+        if let (true, Some(content_encoding)) = (res.status().is_success(), content_encoding) {
+            log::error!("{} was served with the content encoding `{}`", self.video_details.video_id, content_encoding);
+            return Err(Error::UnexpectedContentEncoding(content_encoding));
+        }
+
+        // Expired urls are rejected with 403 as well, which says nothing about the decipher
+        // routine, so they are reported as regular request errors.
+        if res.status() == reqwest::StatusCode::FORBIDDEN && self.is_expired() {
+            log::error!(
+                "the url of {} expired at {:?}",
+                self.video_details.video_id, self.expires_at()
+            );
+        } else if res.status() == reqwest::StatusCode::FORBIDDEN && self.signature_cipher.s.is_some() {
+            log::error!("the deciphered url of {} was rejected", self.video_details.video_id);
+            return Err(Error::Cipher(format!(
+                "YouTube rejected the deciphered url with {}", res.status()
+            ).into()));
+        }
+        if res.status() != reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Ok(res.error_for_status()?);
+        }
+
+        // Retry-After is either a number of seconds, or a HTTP date
+        let retry_after = res
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|retry_after| retry_after.to_str().ok())
+            .and_then(|retry_after| match retry_after.trim().parse::<u64>() {
+                Ok(secs) => Some(Duration::from_secs(secs)),
+                Err(_) => DateTime::parse_from_rfc2822(retry_after)
+                    .ok()
+                    .map(|date| (date.with_timezone(&Utc) - Utc::now()).to_std().unwrap_or_default()),
+            });
+
+        log::warn!("{} was rate limited, retry after: {:?}", res.url(), retry_after);
+        Err(Error::RateLimited { retry_after })
+    }
+
     #[inline]
-    #[allow(unused_variables, unused_mut)]
-    async fn write_stream_to_file(
+    #[allow(unused_variables)]
+    async fn write_stream_to_file<W: AsyncWrite + Unpin + ?Sized>(
         &self,
         mut stream: impl tokio_stream::Stream<Item=reqwest::Result<bytes::Bytes>> + Unpin,
-        file: &mut File,
+        writer: &mut W,
         callback: &Option<Callback>,
-        mut counter: usize,
-    ) -> Result<usize> {
-        // Counter will be 0 if callback is not enabled
+        mut counter: u64,
+    ) -> Result<u64> {
         #[cfg(feature = "callback")]
         let channel = callback
             .as_ref()
-            .map(|c| c.internal_sender.clone());
-        while let Some(chunk) = stream.next().await {
-            let chunk = chunk?;
-            file
+            .and_then(|c| c.internal_sender.clone());
+        let mut buffer = bytes::BytesMut::new();
+        let mut done = false;
+        while !done {
+            let chunk = match stream.next().await {
+                Some(chunk) => {
+                    let chunk = chunk?;
+                    if buffer.len() + chunk.len() < self.read_buffer_size {
+                        buffer.extend_from_slice(&chunk);
+                        continue;
+                    }
+                    if buffer.is_empty() {
+                        chunk
+                    } else {
+                        buffer.extend_from_slice(&chunk);
+                        buffer.split().freeze()
+                    }
+                }
+                None if buffer.is_empty() => break,
+                // the remaining bytes are written, even if they don't fill the buffer
+                None => {
+                    done = true;
+                    buffer.split().freeze()
+                }
+            };
+            writer
                 .write_all(&chunk)
-                .await?;
+                .await
+                .map_err(|e| write_error(e, counter, None))?;
+            counter += chunk.len() as u64;
+            if let Some(ref observer) = self.observer {
+                observer.on_bytes(self, chunk.len());
+            }
             #[cfg(feature = "callback")]
             if let Some(channel) = &channel {
-                counter += chunk.len();
                 // Will continue even if the receiver is closed
                 // Will ignore if the channel is full and thus not slow down the download
                 match channel.try_send(counter) {
@@ -405,24 +2750,32 @@ impl Stream {
             .append_pair("sq", &sq.to_string());
     }
 
-    #[inline]
-    fn extract_segment_count(res: &reqwest::Response) -> Result<u64> {
-        Ok(
-            res
-                .headers()
-                .get("Segment-Count")
-                .ok_or_else(|| Error::UnexpectedResponse(
-                    "sequence download request did not contain a Segment-Count".into()
-                ))?
-                .to_str()
-                .map_err(|_| Error::UnexpectedResponse(
-                    "Segment-Count is not valid utf-8".into()
-                ))?
-                .parse::<u64>()
-                .map_err(|_| Error::UnexpectedResponse(
-                    "Segment-Count could not be parsed into an integer".into()
-                ))?
-        )
+    /// Extracts the `Segment-Count` either from the headers of the response to the 0th sequential
+    /// request, or from the header segment itself.
+    /// Returns `None`, if the server does not advertise the number of segments at all.
+    fn extract_segment_count(headers: &reqwest::header::HeaderMap, header_segment: &[u8]) -> Result<Option<u64>> {
+        static SEGMENT_COUNT: SyncLazy<regex::bytes::Regex> = SyncLazy::new(||
+            regex::bytes::Regex::new(r"Segment-Count: (\d+)").unwrap()
+        );
+
+        let segment_count = match headers.get("Segment-Count") {
+            Some(segment_count) => segment_count.as_bytes(),
+            None => match SEGMENT_COUNT.captures(header_segment).and_then(|c| c.get(1)) {
+                Some(segment_count) => segment_count.as_bytes(),
+                None => return Ok(None),
+            }
+        };
+
+        std::str::from_utf8(segment_count)
+            .map_err(|_| Error::UnexpectedResponse(
+                "Segment-Count is not valid utf-8".into()
+            ))?
+            .trim()
+            .parse::<u64>()
+            .map(Some)
+            .map_err(|_| Error::UnexpectedResponse(
+                "Segment-Count could not be parsed into an integer".into()
+            ))
     }
 }
 
@@ -443,12 +2796,40 @@ impl Stream {
         Ok(crate::block!(self.download_callback(callback))?)
     }
 
+    /// A synchronous wrapper around [`Stream::download_pair_callback`](crate::Stream::download_pair_callback).
+    #[cfg(any(feature = "callback", doc))]
+    #[doc(cfg(feature = "callback"))]
+    #[inline]
+    pub fn blocking_download_pair_callback(
+        video: &Stream,
+        audio: &Stream,
+        callback: Callback,
+    ) -> Result<(PathBuf, PathBuf)> {
+        crate::block!(Self::download_pair_callback(video, audio, callback))
+    }
+
     /// A synchronous wrapper around [`Stream::download_to_dir`](crate::Stream::download_to_dir). 
     #[inline]
     pub fn blocking_download_to_dir<P: AsRef<Path>>(&self, dir: P) -> Result<PathBuf> {
         Ok(crate::block!(self.download_to_dir(dir))?)
     }
 
+    /// A synchronous wrapper around [`Stream::download_to_dir_templated`](crate::Stream::download_to_dir_templated).
+    #[inline]
+    pub fn blocking_download_to_dir_templated<P: AsRef<Path>>(&self, dir: P, template: &str) -> Result<PathBuf> {
+        Ok(crate::block!(self.download_to_dir_templated(dir, template))?)
+    }
+
+    /// A synchronous wrapper around [`Stream::download_all_to_dir`](crate::Stream::download_all_to_dir).
+    #[inline]
+    pub fn blocking_download_all_to_dir<P: AsRef<Path>>(
+        streams: &[Stream],
+        dir: P,
+        concurrency: usize,
+    ) -> Vec<Result<PathBuf>> {
+        crate::block!(Self::download_all_to_dir(streams, dir, concurrency))
+    }
+
     /// A synchronous wrapper around [`Stream::download_to_dir_callback`](crate::Stream::download_to_dir_callback).
     #[cfg(any(feature = "callback", doc))]
     #[doc(cfg(feature = "callback"))]
@@ -462,45 +2843,441 @@ impl Stream {
     }
 
     /// A synchronous wrapper around [`Stream::download_to`](crate::Stream::download_to).
-    pub fn blocking_download_to<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+    pub fn blocking_download_to<P: AsRef<Path>>(&self, path: P) -> Result<u64> {
         Ok(crate::block!(self.download_to(path))?)
     }
 
+    /// A synchronous wrapper around [`Stream::download_to_with_timeout`](crate::Stream::download_to_with_timeout).
+    #[inline]
+    pub fn blocking_download_to_with_timeout<P: AsRef<Path>>(&self, path: P, timeout: Duration) -> Result<u64> {
+        crate::block!(self.download_to_with_timeout(path, timeout))
+    }
+
+    /// A synchronous wrapper around [`Stream::download_to_with_opener`](crate::Stream::download_to_with_opener).
+    #[inline]
+    pub fn blocking_download_to_with_opener<P, W, F>(&self, path: P, open: F) -> Result<u64>
+        where
+            P: AsRef<Path>,
+            W: AsyncWrite + Unpin,
+            F: FnOnce(&Path) -> Result<W> {
+        crate::block!(self.download_to_with_opener(path, open))
+    }
+
+    /// A synchronous wrapper around [`Stream::download_to_with_client`](crate::Stream::download_to_with_client).
+    #[inline]
+    pub fn blocking_download_to_with_client<P: AsRef<Path>>(&self, path: P, client: &Client) -> Result<u64> {
+        crate::block!(self.download_to_with_client(path, client))
+    }
+
+    /// A synchronous wrapper around [`Stream::download_to_cancellable`](crate::Stream::download_to_cancellable).
+    #[inline]
+    pub fn blocking_download_to_cancellable<P: AsRef<Path>>(&self, path: P, token: CancellationToken) -> Result<u64> {
+        crate::block!(self.download_to_cancellable(path, token))
+    }
+
+    /// A synchronous wrapper around [`Stream::download_to_cancellable_callback`](crate::Stream::download_to_cancellable_callback).
+    #[cfg(any(feature = "callback", doc))]
+    #[doc(cfg(feature = "callback"))]
+    #[inline]
+    pub fn blocking_download_to_cancellable_callback<P: AsRef<Path>>(
+        &self,
+        path: P,
+        callback: Callback,
+        token: CancellationToken,
+    ) -> Result<u64> {
+        crate::block!(self.download_to_cancellable_callback(path, callback, token))
+    }
+
+    /// A synchronous wrapper around [`Stream::download_to_many`](crate::Stream::download_to_many).
+    #[inline]
+    pub fn blocking_download_to_many<P: AsRef<Path>>(&self, paths: &[P]) -> Result<u64> {
+        crate::block!(self.download_to_many(paths))
+    }
+
+    /// A synchronous wrapper around [`Stream::download_to_buffered`](crate::Stream::download_to_buffered).
+    #[inline]
+    pub fn blocking_download_to_buffered<P: AsRef<Path>>(&self, path: P, write_buffer_size: usize) -> Result<u64> {
+        crate::block!(self.download_to_buffered(path, write_buffer_size))
+    }
+
     /// A synchronous wrapper around [`Stream::download_to_callback`](crate::Stream::download_to_callback).
     #[cfg(any(feature = "callback", doc))]
     #[doc(cfg(feature = "callback"))]
-    pub fn blocking_download_to_callback<P: AsRef<Path>>(&self, path: P, callback: Callback) -> Result<()> {
+    pub fn blocking_download_to_callback<P: AsRef<Path>>(&self, path: P, callback: Callback) -> Result<u64> {
         Ok(crate::block!(self.download_to_callback(path, callback))?)
     }
 
+    /// A synchronous wrapper around [`Stream::download_range_to`](crate::Stream::download_range_to).
+    #[inline]
+    pub fn blocking_download_range_to<P: AsRef<Path>>(&self, path: P, range: Range<u64>) -> Result<u64> {
+        crate::block!(self.download_range_to(path, range))
+    }
+
+    /// A synchronous wrapper around [`Stream::download_to_writer`](crate::Stream::download_to_writer).
+    #[inline]
+    pub fn blocking_download_to_writer<W: AsyncWrite + Unpin + ?Sized>(&self, writer: &mut W) -> Result<u64> {
+        crate::block!(self.download_to_writer(writer))
+    }
+
     /// A synchronous wrapper around [`Stream::content_length`](crate::Stream::content_length).
     #[inline]
     pub fn blocking_content_length(&self) -> Result<u64> {
         crate::block!(self.content_length())
     }
+
+    /// A synchronous wrapper around [`Stream::probe`](crate::Stream::probe).
+    #[inline]
+    pub fn blocking_probe(&self) -> Result<ProbeResult> {
+        crate::block!(self.probe())
+    }
+
+    /// A synchronous wrapper around [`Stream::download_to_if_modified_since`](crate::Stream::download_to_if_modified_since).
+    #[inline]
+    pub fn blocking_download_to_if_modified_since<P: AsRef<Path>>(
+        &self,
+        path: P,
+        since: DateTime<Utc>,
+    ) -> Result<DownloadOutcome> {
+        crate::block!(self.download_to_if_modified_since(path, since))
+    }
+
+    /// A synchronous wrapper around [`Stream::download_with`](crate::Stream::download_with).
+    #[inline]
+    pub fn blocking_download_with(&self, options: DownloadOptions) -> Result<PathBuf> {
+        crate::block!(self.download_with(options))
+    }
+
+    /// A synchronous wrapper around [`Stream::download_to_pausable`](crate::Stream::download_to_pausable).
+    #[inline]
+    pub fn blocking_download_to_pausable<P: AsRef<Path>>(&self, path: P, handle: PauseHandle) -> Result<u64> {
+        crate::block!(self.download_to_pausable(path, handle))
+    }
+
+    /// A synchronous wrapper around [`Stream::download_segments_to`](crate::Stream::download_segments_to).
+    #[inline]
+    pub fn blocking_download_segments_to<P: AsRef<Path>>(&self, path: P, max_segments: u64) -> Result<u64> {
+        crate::block!(self.download_segments_to(path, max_segments))
+    }
+
+    /// A synchronous wrapper around [`Stream::download_seq_resumable_to`](crate::Stream::download_seq_resumable_to).
+    #[inline]
+    pub fn blocking_download_seq_resumable_to<P: AsRef<Path>>(&self, path: P) -> Result<u64> {
+        crate::block!(self.download_seq_resumable_to(path))
+    }
+
+    /// A synchronous wrapper around [`Stream::download_to_stdout`](crate::Stream::download_to_stdout).
+    #[inline]
+    pub fn blocking_download_to_stdout(&self) -> Result<u64> {
+        crate::block!(self.download_to_stdout())
+    }
+
+    /// A synchronous wrapper around [`Stream::download_bytes`](crate::Stream::download_bytes).
+    #[inline]
+    pub fn blocking_download_bytes(&self) -> Result<Vec<u8>> {
+        crate::block!(self.download_bytes())
+    }
+
+    /// A synchronous wrapper around [`Stream::download_bytes_with_limit`](crate::Stream::download_bytes_with_limit).
+    #[inline]
+    pub fn blocking_download_bytes_with_limit(&self, max_size: u64) -> Result<Vec<u8>> {
+        crate::block!(self.download_bytes_with_limit(max_size))
+    }
+
+    /// A synchronous wrapper around [`Stream::download_thumbnail`](crate::Stream::download_thumbnail).
+    #[inline]
+    pub fn blocking_download_thumbnail(&self) -> Result<Vec<u8>> {
+        crate::block!(self.download_thumbnail())
+    }
+
+    /// A synchronous wrapper around [`Stream::is_available`](crate::Stream::is_available).
+    #[inline]
+    pub fn blocking_is_available(&self) -> bool {
+        crate::block!(self.is_available())
+    }
+
+    /// A synchronous wrapper around [`Stream::head_info`](crate::Stream::head_info).
+    #[inline]
+    pub fn blocking_head_info(&self) -> Result<HeadInfo> {
+        crate::block!(self.head_info())
+    }
+
+    /// A synchronous wrapper around [`Stream::supports_ranges`](crate::Stream::supports_ranges).
+    #[inline]
+    pub fn blocking_supports_ranges(&self) -> Result<bool> {
+        crate::block!(self.supports_ranges())
+    }
+
+    /// A synchronous wrapper around [`Stream::fill_content_lengths`](crate::Stream::fill_content_lengths).
+    #[inline]
+    pub fn blocking_fill_content_lengths(streams: &[Stream], concurrency: usize) -> Vec<Result<u64>> {
+        crate::block!(Self::fill_content_lengths(streams, concurrency))
+    }
 }
 
+/// The lowest framerate, that's considered a high framerate by [`Stream::is_high_framerate`].
+const HIGH_FRAMERATE: u8 = 48;
+
+/// The height [`Stream::auto_default`] aims for.
+const AUTO_DEFAULT_HEIGHT: u64 = 720;
+
+/// The maximum number of bytes [`Stream::download_bytes`] keeps in memory.
+#[cfg(any(feature = "download", doc))]
+#[doc(cfg(feature = "download"))]
+pub const DEFAULT_MAX_BYTES: u64 = 256 * 1024 * 1024;
+
+/// The size of the buffer downloads are written through, if not specified otherwise.
+#[cfg(any(feature = "download", doc))]
+const DEFAULT_WRITE_BUFFER_SIZE: usize = 64 * 1024;
+
+/// The maximum number of segments of a sequenced download, that are requested concurrently, if
+/// not specified otherwise (see [`Stream::with_sequenced_window`]).
+const SEQUENCED_WINDOW: usize = 4;
+
+/// Codec prefixes, that identify a video track.
+const VIDEO_CODECS: &[&str] = &["avc1", "avc3", "av01", "vp8", "vp9", "vp09", "hev1", "hvc1", "mp4v"];
+/// Codec prefixes, that identify an audio track.
+const AUDIO_CODECS: &[&str] = &["mp4a", "opus", "vorbis", "ac-3", "ec-3", "flac"];
+
 #[inline]
-fn is_adaptive(codecs: &Vec<String>) -> bool {
-    codecs.len() % 2 != 0
+fn has_codec(codecs: &Vec<String>, known: &[&str]) -> bool {
+    codecs
+        .iter()
+        .map(|codec| codec.trim())
+        .any(|codec| known.iter().any(|prefix| codec.starts_with(prefix)))
 }
 
 #[inline]
 fn includes_video_track(codecs: &Vec<String>, mime: &Mime) -> bool {
-    is_progressive(codecs) || mime.type_() == "video"
+    // if none of the codecs is known, the mime type is the best guess we have
+    has_codec(codecs, VIDEO_CODECS) || (mime.type_() == "video" && !has_codec(codecs, AUDIO_CODECS))
 }
 
 #[inline]
 fn includes_audio_track(codecs: &Vec<String>, mime: &Mime) -> bool {
-    is_progressive(codecs) || mime.type_() == "audio"
+    has_codec(codecs, AUDIO_CODECS) || (mime.type_() == "audio" && !has_codec(codecs, VIDEO_CODECS))
+}
+
+#[inline]
+fn is_progressive(codecs: &Vec<String>, mime: &Mime) -> bool {
+    includes_video_track(codecs, mime) && includes_audio_track(codecs, mime)
+}
+
+/// Replaces all characters, which are invalid in file names on common platforms, with `_`.
+#[cfg(any(feature = "download", doc))]
+fn sanitize_file_name(file_name: &str) -> String {
+    file_name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c
+        })
+        .collect()
+}
+
+/// Extracts the file name from a `Content-Disposition` header value, preferring the (percent
+/// encoded) `filename*` parameter over the plain `filename` parameter.
+/// Only the last path component is kept, so the server cannot choose the directory.
+#[cfg(any(feature = "download", doc))]
+fn content_disposition_file_name(content_disposition: &str) -> Option<String> {
+    fn percent_decode(s: &str) -> String {
+        let bytes = s.as_bytes();
+        let mut decoded = Vec::with_capacity(bytes.len());
+        let mut i = 0;
+        while i < bytes.len() {
+            let hex = bytes.get(i + 1..i + 3)
+                .and_then(|hex| std::str::from_utf8(hex).ok())
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+            match (bytes[i], hex) {
+                (b'%', Some(byte)) => {
+                    decoded.push(byte);
+                    i += 3;
+                }
+                (byte, _) => {
+                    decoded.push(byte);
+                    i += 1;
+                }
+            }
+        }
+        String::from_utf8_lossy(&decoded).into_owned()
+    }
+
+    let params = content_disposition
+        .split(';')
+        .skip(1)
+        .filter_map(|param| {
+            let mut param = param.splitn(2, '=');
+            Some((param.next()?.trim().to_ascii_lowercase(), param.next()?.trim()))
+        })
+        .collect::<Vec<_>>();
+
+    let file_name = params
+        .iter()
+        .find(|(key, _)| key == "filename*")
+        // filename*=<charset>'<language>'<percent encoded name>
+        .and_then(|(_, value)| value.splitn(3, '\'').nth(2))
+        .map(percent_decode)
+        .or_else(|| params
+            .iter()
+            .find(|(key, _)| key == "filename")
+            .map(|(_, value)| value.trim_matches('"').to_owned())
+        )?;
+
+    let file_name = file_name
+        .rsplit(|c| c == '/' || c == '\\')
+        .next()
+        .map(sanitize_file_name)?;
+    match file_name.trim() {
+        "" | "." | ".." => None,
+        file_name => Some(file_name.to_owned()),
+    }
+}
+
+/// Wraps an io error, that occurred while writing a download, into [`Error::Write`].
+/// Since writes may be buffered, `offset` is the number of bytes, that were handed to the
+/// writer successfully, before the error occurred.
+#[cfg(any(feature = "download", doc))]
+#[inline]
+fn write_error(source: std::io::Error, offset: u64, path: Option<&Path>) -> Error {
+    Error::Write {
+        offset,
+        path: path.map(Path::to_path_buf),
+        source,
+    }
+}
+
+/// Adds the `path`, that was written to, to an [`Error::Write`], that doesn't know it yet.
+#[cfg(any(feature = "download", doc))]
+#[inline]
+fn with_write_path(error: Error, path: &Path) -> Error {
+    match error {
+        Error::Write { offset, path: None, source } => write_error(source, offset, Some(path)),
+        error => error,
+    }
+}
+
+#[cfg(any(feature = "download", doc))]
+#[inline]
+fn accepts_ranges(headers: &reqwest::header::HeaderMap) -> bool {
+    headers
+        .get(reqwest::header::ACCEPT_RANGES)
+        .and_then(|ar| ar.to_str().ok())
+        .map_or(false, |ar| ar.trim().eq_ignore_ascii_case("bytes"))
+}
+
+/// Moves the file at `from` to `to`.
+///
+/// Files cannot be renamed across file systems, so if `from` is on another file system than
+/// `to`, the file is copied next to `to` first (to `<to>.<pid>.part`), which is then renamed to
+/// `to`. This way, there's never a partially copied file at `to`.
+#[cfg(any(feature = "download", doc))]
+async fn move_file(from: &Path, to: &Path) -> std::io::Result<()> {
+    // `EXDEV` on unix, and `ERROR_NOT_SAME_DEVICE` on windows
+    #[cfg(unix)]
+    const CROSSES_DEVICES: i32 = 18;
+    #[cfg(windows)]
+    const CROSSES_DEVICES: i32 = 17;
+
+    match tokio::fs::rename(from, to).await {
+        #[cfg(any(unix, windows))]
+        Err(e) if e.raw_os_error() == Some(CROSSES_DEVICES) => {
+            log::debug!("{:?} and {:?} are on different file systems, copying the file instead", from, to);
+            let part_path = part_path(to);
+            let copy = async {
+                tokio::fs::copy(from, &part_path).await?;
+                tokio::fs::rename(&part_path, to).await
+            };
+            if let Err(e) = copy.await {
+                let _ = tokio::fs::remove_file(&part_path).await;
+                return Err(e);
+            }
+            tokio::fs::remove_file(from).await
+        }
+        result => result,
+    }
+}
+
+/// Converts a failed request into an [`Error`], surfacing redirect loops as
+/// [`Error::TooManyRedirects`].
+#[cfg(any(feature = "download", doc))]
+#[inline]
+fn request_error(error: reqwest::Error) -> Error {
+    if error.is_redirect() {
+        log::error!("request to {:?} was redirected too often", error.url().map(url::Url::as_str));
+        return Error::TooManyRedirects(error.url().cloned());
+    }
+    Error::Request(error)
+}
+
+/// Whether or not the request failed because of a connection error, a timeout, or a server
+/// error, so retrying it may succeed.
+#[cfg(any(feature = "download", doc))]
+#[inline]
+fn is_transient(error: &Error) -> bool {
+    match error {
+        Error::Request(e) => e.is_connect() || e.is_timeout() || e.status().map_or(false, |s| s.is_server_error()),
+        _ => false,
+    }
 }
 
+/// The temporary path a download is written to, before it's renamed to `path`:
+/// `<path>.<pid>.part`.
+/// The process id prevents two processes, which download to the same path, from writing to the
+/// same temporary file.
+#[cfg(any(feature = "download", doc))]
 #[inline]
-fn is_progressive(codecs: &Vec<String>) -> bool {
-    !is_adaptive(codecs)
+fn part_path(path: &Path) -> PathBuf {
+    let mut file_name = path
+        .file_name()
+        .map(std::ffi::OsStr::to_os_string)
+        .unwrap_or_default();
+    file_name.push(format!(".{}.part", std::process::id()));
+    path.with_file_name(file_name)
+}
+
+/// What a download requests, and writes to its file.
+#[cfg(any(feature = "download", doc))]
+enum DownloadBody<'a> {
+    /// The whole resource.
+    Full,
+    /// The whole resource, from a response, that was already received.
+    Response(reqwest::Response),
+    /// Only the byte range, using a single `Range` request.
+    Range(Range<u64>),
+    /// The rest of the resource, after the first `offset` bytes, which were already written.
+    From(u64),
+    /// The whole resource, which can be paused and resumed using the handle.
+    Pausable(PauseHandle),
+    /// The header and the first segments of a sequenced resource.
+    Segments(u64),
+    /// The segments of a sequenced resource, after the last segment recorded in the progress.
+    Sequenced(&'a mut segment_progress::SegmentProgress),
+}
+
+#[cfg(any(feature = "download", doc))]
+impl DownloadBody<'_> {
+    /// The number of bytes, that were already written before the download started.
+    fn offset(&self) -> u64 {
+        match self {
+            Self::From(offset) => *offset,
+            Self::Sequenced(progress) => progress.bytes(),
+            _ => 0,
+        }
+    }
+}
+
+/// Removes the partially downloaded file at `path`, after a download failed.
+///
+/// A failure to remove the file is only logged, so it doesn't hide the error, that made the
+/// download fail in the first place.
+#[cfg(any(feature = "download", doc))]
+async fn remove_part_file(path: &Path) {
+    if let Err(e) = tokio::fs::remove_file(path).await {
+        log::warn!("failed to remove the partially downloaded file {:?}: {}", path, e);
+    }
 }
 
 #[inline]
-fn atomic_u64_is_eq(lhs: &Arc<AtomicU64>, rhs: &Arc<AtomicU64>) -> bool {
-    lhs.load(Ordering::Acquire) == rhs.load(Ordering::Acquire)
+fn once_cell_is_eq(lhs: &Arc<OnceCell<u64>>, rhs: &Arc<OnceCell<u64>>) -> bool {
+    lhs.get() == rhs.get()
 }
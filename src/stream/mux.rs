@@ -0,0 +1,121 @@
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+
+use tokio::process::Command;
+
+use crate::{Error, Result};
+
+use super::Stream;
+
+#[cfg(windows)]
+const FFMPEG: &str = "ffmpeg.exe";
+#[cfg(not(windows))]
+const FFMPEG: &str = "ffmpeg";
+
+#[doc(cfg(feature = "mux"))]
+impl Stream {
+    /// Downloads this [`Stream`]s video track and the provided `audio` [`Stream`]s audio track,
+    /// and muxes them into a single file at `output`.
+    ///
+    /// This is mainly useful for adaptive streams, since the best qualities YouTube offers are
+    /// usually only available as separate video-only and audio-only streams.
+    ///
+    /// The muxing is done by an `ffmpeg` binary, which is looked up in the `PATH`. If you want to
+    /// use a specific binary, have a look at [`Stream::mux_with_ffmpeg`].
+    /// Both tracks are downloaded next to `output`, and removed afterwards, even if muxing them
+    /// failed.
+    ///
+    /// ### Errors
+    /// - When no `ffmpeg` binary could be found in the `PATH`.
+    /// - When downloading one of the tracks fails.
+    /// - When `ffmpeg` exits unsuccessfully.
+    #[inline]
+    pub async fn mux_with<P: AsRef<Path>>(&self, audio: &Stream, output: P) -> Result<()> {
+        let ffmpeg = find_ffmpeg().ok_or(Error::FfmpegNotFound)?;
+        self.mux_with_ffmpeg(audio, output, ffmpeg).await
+    }
+
+    /// Like [`Stream::mux_with`], but uses the `ffmpeg` binary at the provided path, instead of
+    /// looking it up in the `PATH`.
+    ///
+    /// ### Errors
+    /// - When there's no `ffmpeg` binary at the provided path.
+    /// - When downloading one of the tracks fails.
+    /// - When `ffmpeg` exits unsuccessfully.
+    pub async fn mux_with_ffmpeg<P: AsRef<Path>, F: AsRef<Path>>(
+        &self,
+        audio: &Stream,
+        output: P,
+        ffmpeg: F,
+    ) -> Result<()> {
+        let output = output.as_ref();
+
+        let video_path = self.track_path(output);
+        let audio_path = audio.track_path(output);
+
+        let mux = async {
+            tokio::try_join!(
+                self.download_to(&video_path),
+                audio.download_to(&audio_path)
+            )?;
+
+            log::trace!("mux {:?} and {:?} into {:?}", video_path, audio_path, output);
+            let out = Command::new(ffmpeg.as_ref())
+                .args(&["-y", "-loglevel", "error"])
+                .arg("-i").arg(&video_path)
+                .arg("-i").arg(&audio_path)
+                .args(&["-map", "0:v:0", "-map", "1:a:0", "-c", "copy"])
+                .arg(output)
+                .output()
+                .await
+                .map_err(|e| match e.kind() {
+                    std::io::ErrorKind::NotFound => Error::FfmpegNotFound,
+                    _ => Error::IO(e)
+                })?;
+
+            if !out.status.success() {
+                log::error!("ffmpeg failed to mux {:?} and {:?}", video_path, audio_path);
+                return Err(Error::Ffmpeg(
+                    out.status,
+                    String::from_utf8_lossy(&out.stderr).into_owned(),
+                ));
+            }
+            Ok(())
+        };
+        let result = mux.await;
+
+        // The tracks are only needed for muxing, so they are removed in any case. If one of the
+        // downloads failed, the other one was aborted, and may have left its part file behind.
+        for track_path in [&video_path, &audio_path].iter() {
+            for path in [track_path.to_path_buf(), super::part_path(track_path)].iter() {
+                // files, that were never created, don't exist
+                let _ = tokio::fs::remove_file(path).await;
+            }
+        }
+
+        if result.is_ok() {
+            log::info!("muxed {} successfully to {:?}", self.video_details.video_id, output);
+        }
+        result
+    }
+
+    /// The path an intermediate track is downloaded to before muxing:
+    /// `<output>.f<itag>.<extension>`.
+    #[inline]
+    fn track_path(&self, output: &Path) -> PathBuf {
+        let mut file_name = output
+            .file_name()
+            .map(OsStr::to_os_string)
+            .unwrap_or_default();
+        file_name.push(format!(".f{}.{}", self.itag, self.file_extension()));
+        output.with_file_name(file_name)
+    }
+}
+
+/// Searches the `PATH` for an `ffmpeg` binary.
+fn find_ffmpeg() -> Option<PathBuf> {
+    let path = std::env::var_os("PATH")?;
+    std::env::split_paths(&path)
+        .map(|dir| dir.join(FFMPEG))
+        .find(|ffmpeg| ffmpeg.is_file())
+}
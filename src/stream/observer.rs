@@ -0,0 +1,54 @@
+use std::time::Duration;
+
+use crate::Error;
+
+use super::Stream;
+
+/// Hooks, which are called while a [`Stream`] is downloaded, i.e. to export metrics.
+///
+/// All hooks do nothing by default, so an implementation only has to override the ones it's
+/// interested in. Attach an implementation to a [`Stream`] using
+/// [`Stream::with_observer`](super::Stream::with_observer).
+///
+/// The hooks are called synchronously from within the download, so they should return quickly,
+/// i.e. by only updating counters.
+///
+/// ```
+///# use std::sync::atomic::{AtomicU64, Ordering};
+///# use rustube::{DownloadObserver, Stream};
+/// #[derive(Default)]
+/// struct Metrics {
+///     requests: AtomicU64,
+///     bytes: AtomicU64,
+/// }
+///
+/// impl DownloadObserver for Metrics {
+///     fn on_request_start(&self, _stream: &Stream, _request: &reqwest::Request) {
+///         self.requests.fetch_add(1, Ordering::Relaxed);
+///     }
+///
+///     fn on_bytes(&self, _stream: &Stream, bytes: usize) {
+///         self.bytes.fetch_add(bytes as u64, Ordering::Relaxed);
+///     }
+/// }
+/// ```
+pub trait DownloadObserver: Send + Sync {
+    /// Called right before a request is sent, including `HEAD` requests, every segment of a
+    /// sequenced download, and requests retried through another proxy.
+    #[inline]
+    fn on_request_start(&self, _stream: &Stream, _request: &reqwest::Request) {}
+
+    /// Called whenever `bytes` bytes of the [`Stream`] were written.
+    #[inline]
+    fn on_bytes(&self, _stream: &Stream, _bytes: usize) {}
+
+    /// Called when [`Stream::download_with`](super::Stream::download_with) retries a download,
+    /// that failed with `error`. `attempt` starts at `1` for the first retry.
+    #[inline]
+    fn on_retry(&self, _stream: &Stream, _attempt: usize, _error: &Error) {}
+
+    /// Called once a download to a file finished, either with the number of bytes written, or
+    /// the error the download failed with, and how long the download took.
+    #[inline]
+    fn on_finish(&self, _stream: &Stream, _result: Result<u64, &Error>, _duration: Duration) {}
+}
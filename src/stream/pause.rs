@@ -0,0 +1,75 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use tokio::sync::Notify;
+
+/// A handle to pause and resume a download started with
+/// [`Stream::download_to_pausable`](super::Stream::download_to_pausable).
+///
+/// The handle can be cloned, and all clones control the same download, so one clone can be moved
+/// into the download, while another one is kept to i.e. react to user input.
+#[derive(Clone, Debug, Default)]
+pub struct PauseHandle {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    paused: AtomicBool,
+    notify: Notify,
+}
+
+impl PauseHandle {
+    /// Creates a new [`PauseHandle`], which is not paused.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pauses the download. Pausing an already paused download has no effect.
+    #[inline]
+    pub fn pause(&self) {
+        self.set_paused(true);
+    }
+
+    /// Resumes the download. Resuming a download, which is not paused, has no effect.
+    #[inline]
+    pub fn resume(&self) {
+        self.set_paused(false);
+    }
+
+    /// Whether or not the download is currently paused.
+    #[inline]
+    pub fn is_paused(&self) -> bool {
+        self.inner.paused.load(Ordering::SeqCst)
+    }
+
+    #[inline]
+    fn set_paused(&self, paused: bool) {
+        self.inner.paused.store(paused, Ordering::SeqCst);
+        self.inner.notify.notify_waiters();
+    }
+
+    /// Resolves, once the download is paused.
+    #[inline]
+    pub(super) async fn paused(&self) {
+        self.wait_for(true).await
+    }
+
+    /// Resolves, once the download is not paused (anymore).
+    #[inline]
+    pub(super) async fn resumed(&self) {
+        self.wait_for(false).await
+    }
+
+    async fn wait_for(&self, paused: bool) {
+        loop {
+            // the future has to be created before checking the state, so no notification is missed
+            let notified = self.inner.notify.notified();
+            if self.is_paused() == paused {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use reqwest::{Client, Proxy};
+
+use crate::{Error, Result};
+
+/// A pool of proxies, which [`Stream`](super::Stream)s rotate through, when sending requests.
+///
+/// Every request is sent through the next proxy in the pool (round-robin). When a request fails
+/// because the proxy could not be reached, or timed out, it's retried through the next proxy,
+/// until every proxy was tried once.
+///
+/// Attach a [`ProxyPool`] to a [`Stream`](super::Stream) using
+/// [`Stream::with_proxy_pool`](super::Stream::with_proxy_pool).
+#[derive(Debug)]
+pub struct ProxyPool {
+    clients: Vec<Client>,
+    next: AtomicUsize,
+}
+
+impl ProxyPool {
+    /// Creates a [`ProxyPool`] with one [`Client`] per proxy URL.
+    /// The [`Client`]s use the [`recommended_headers`](crate::fetcher::recommended_headers).
+    ///
+    /// ### Errors
+    /// - When `proxies` is empty.
+    /// - When one of the proxy URLs is invalid.
+    /// - When [`reqwest`] fails to initialize a new [`Client`].
+    pub fn new<I, U>(proxies: I) -> Result<Self>
+        where
+            I: IntoIterator<Item=U>,
+            U: reqwest::IntoUrl {
+        let clients = proxies
+            .into_iter()
+            .map(|proxy| {
+                Ok(
+                    Client::builder()
+                        .default_headers(crate::fetcher::recommended_headers())
+                        .proxy(Proxy::all(proxy)?)
+                        .build()?
+                )
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Self::from_clients(clients)
+    }
+
+    /// Creates a [`ProxyPool`] from already configured [`Client`]s, i.e. with different proxies.
+    ///
+    /// ### Errors
+    /// - When `clients` is empty.
+    pub fn from_clients(clients: Vec<Client>) -> Result<Self> {
+        if clients.is_empty() {
+            return Err(Error::Custom("a ProxyPool needs at least one proxy".into()));
+        }
+
+        Ok(Self {
+            clients,
+            next: AtomicUsize::new(0),
+        })
+    }
+
+    /// The [`Client`] the next request should be sent with.
+    #[inline]
+    pub fn next_client(&self) -> &Client {
+        let next = self.next.fetch_add(1, Ordering::Relaxed);
+        &self.clients[next % self.clients.len()]
+    }
+
+    /// The number of proxies in the pool.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.clients.len()
+    }
+
+    /// Whether the pool contains no proxies, which is never the case, since
+    /// [`ProxyPool::from_clients`] rejects empty pools.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.clients.is_empty()
+    }
+}
@@ -0,0 +1,88 @@
+use std::path::{Path, PathBuf};
+
+use crate::Result;
+
+/// Keeps track of the last sequenced segment, which was completely written to a file, so an
+/// interrupted sequenced download can be resumed from the next segment.
+///
+/// The progress is persisted in a small manifest next to the downloaded file
+/// (`<path>.seq`), which contains the index of the last written segment, and the number of bytes
+/// the file had after writing it.
+#[derive(Debug)]
+pub(super) struct SegmentProgress {
+    manifest: PathBuf,
+    last_segment: Option<u64>,
+    bytes: u64,
+}
+
+impl SegmentProgress {
+    /// Creates a new [`SegmentProgress`] for a download to `path`, which hasn't written any
+    /// segment yet.
+    #[inline]
+    pub(super) fn new(path: &Path) -> Self {
+        Self {
+            manifest: manifest_path(path),
+            last_segment: None,
+            bytes: 0,
+        }
+    }
+
+    /// Loads the progress of a previous download to `path`.
+    /// Returns `None`, if there's no manifest, or it cannot be parsed, in which case the
+    /// download has to start from the beginning.
+    pub(super) async fn load(path: &Path) -> Option<Self> {
+        let manifest = manifest_path(path);
+        let content = tokio::fs::read_to_string(&manifest).await.ok()?;
+        let mut parts = content.split_whitespace();
+        let last_segment = parts.next()?.parse().ok()?;
+        let bytes = parts.next()?.parse().ok()?;
+
+        Some(Self {
+            manifest,
+            last_segment: Some(last_segment),
+            bytes,
+        })
+    }
+
+    /// The index of the next segment, that has to be downloaded.
+    /// The header segment has the index `0`.
+    #[inline]
+    pub(super) fn next_segment(&self) -> u64 {
+        self.last_segment.map_or(0, |last| last + 1)
+    }
+
+    /// The number of bytes, which were written up to, and including, the last segment.
+    #[inline]
+    pub(super) fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    /// Records, that `segment` was completely written, and the file now has `bytes` bytes.
+    /// The writer has to be flushed before calling this method.
+    pub(super) async fn record(&mut self, segment: u64, bytes: u64) -> Result<()> {
+        tokio::fs::write(&self.manifest, format!("{} {}", segment, bytes)).await?;
+        self.last_segment = Some(segment);
+        self.bytes = bytes;
+        Ok(())
+    }
+
+    /// Removes the manifest, once the download has finished.
+    #[inline]
+    pub(super) async fn finish(self) -> Result<()> {
+        match tokio::fs::remove_file(&self.manifest).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// The path of the manifest belonging to a download to `path`: `<path>.seq`.
+#[inline]
+fn manifest_path(path: &Path) -> PathBuf {
+    let mut file_name = path
+        .file_name()
+        .map(std::ffi::OsStr::to_os_string)
+        .unwrap_or_default();
+    file_name.push(".seq");
+    path.with_file_name(file_name)
+}
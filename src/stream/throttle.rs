@@ -0,0 +1,72 @@
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use tokio::io::AsyncWrite;
+use tokio::time::Sleep;
+
+/// An [`AsyncWrite`], that limits the rate at which bytes are written to the inner writer.
+///
+/// Without a rate, all writes are passed through unchanged.
+pub(super) struct Throttled<W> {
+    inner: W,
+    bytes_per_sec: Option<u64>,
+    start: Option<Instant>,
+    written: u64,
+    sleep: Option<Pin<Box<Sleep>>>,
+}
+
+impl<W> Throttled<W> {
+    #[inline]
+    pub(super) fn new(inner: W, bytes_per_sec: Option<u64>) -> Self {
+        Self {
+            inner,
+            bytes_per_sec: bytes_per_sec.filter(|&bytes_per_sec| bytes_per_sec > 0),
+            start: None,
+            written: 0,
+            sleep: None,
+        }
+    }
+}
+
+impl<W: AsyncWrite + Unpin> AsyncWrite for Throttled<W> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+
+        if let Some(bytes_per_sec) = this.bytes_per_sec {
+            if this.sleep.is_none() {
+                // the time it should have taken to write everything so far at the given rate
+                let start = *this.start.get_or_insert_with(Instant::now);
+                let target = Duration::from_secs_f64(this.written as f64 / bytes_per_sec as f64);
+                if let Some(ahead) = target.checked_sub(start.elapsed()) {
+                    this.sleep = Some(Box::pin(tokio::time::sleep(ahead)));
+                }
+            }
+
+            if let Some(sleep) = this.sleep.as_mut() {
+                match sleep.as_mut().poll(cx) {
+                    Poll::Ready(()) => this.sleep = None,
+                    Poll::Pending => return Poll::Pending,
+                }
+            }
+        }
+
+        let written = Pin::new(&mut this.inner).poll_write(cx, buf);
+        if let Poll::Ready(Ok(n)) = written {
+            this.written += n as u64;
+        }
+        written
+    }
+
+    #[inline]
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    #[inline]
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_shutdown(cx)
+    }
+}
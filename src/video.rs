@@ -138,6 +138,21 @@ impl Video {
         self.streams
     }
 
+    /// Attempts to download all [`Stream`]s of the video to the provided directory, with at most
+    /// `concurrency` downloads running at the same time.
+    ///
+    /// See [`Stream::download_all_to_dir`] for details.
+    #[inline]
+    #[cfg(any(feature = "download", doc))]
+    #[doc(cfg(feature = "download"))]
+    pub async fn download_all_to_dir<P: AsRef<std::path::Path>>(
+        &self,
+        dir: P,
+        concurrency: usize,
+    ) -> Vec<crate::Result<std::path::PathBuf>> {
+        Stream::download_all_to_dir(&self.streams, dir, concurrency).await
+    }
+
     /// The [`VideoDetails`]s of the video.
     #[inline]
     pub fn video_details(&self) -> Arc<VideoDetails> {
@@ -67,6 +67,50 @@ pub struct RawFormat {
 pub struct SignatureCipher {
     pub url: Url,
     pub s: Option<String>,
+    /// The name of the query parameter the deciphered signature is added to the `url` as.
+    #[serde(default)]
+    pub sp: Option<String>,
+}
+
+impl SignatureCipher {
+    /// The query parameter used, if YouTube doesn't specify one with `sp`.
+    pub const DEFAULT_SIGNATURE_PARAM: &'static str = "sig";
+
+    /// The name of the query parameter the deciphered signature is added to the
+    /// [`url`](SignatureCipher::url) as, i.e. `sig`, or `signature`.
+    #[inline]
+    pub fn signature_param(&self) -> &str {
+        self.sp.as_deref().unwrap_or(Self::DEFAULT_SIGNATURE_PARAM)
+    }
+
+    /// The signature, if the url needs to be signed.
+    ///
+    /// Before the video was descrambled, this is the encrypted signature YouTube sent. Afterwards,
+    /// it's the deciphered signature, which was added to the [`url`](SignatureCipher::url).
+    #[inline]
+    pub fn signature(&self) -> Option<&str> {
+        self.s.as_deref()
+    }
+
+    /// The [`url`](SignatureCipher::url) without the signature query parameter, i.e. the url as
+    /// it was before signing it.
+    pub fn unsigned_url(&self) -> Url {
+        let param = self.signature_param();
+        let mut url = self.url.clone();
+        let query: Vec<(String, String)> = self.url
+            .query_pairs()
+            .filter(|(key, _)| key != param)
+            .map(|(key, value)| (key.into_owned(), value.into_owned()))
+            .collect();
+        if query.is_empty() {
+            url.set_query(None);
+        } else {
+            url.query_pairs_mut()
+                .clear()
+                .extend_pairs(query);
+        }
+        url
+    }
 }
 
 #[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
@@ -86,19 +130,37 @@ pub struct ColorInfo {
 #[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
 pub enum ColorInfoPrimary {
     #[serde(rename = "COLOR_PRIMARIES_BT709")]
-    BT709
+    BT709,
+    #[serde(rename = "COLOR_PRIMARIES_BT2020")]
+    BT2020,
 }
 
 #[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
 pub enum TransferCharacteristics {
     #[serde(rename = "COLOR_TRANSFER_CHARACTERISTICS_BT709")]
-    BT709
+    BT709,
+    /// Perceptual Quantizer (PQ), used by HDR10.
+    #[serde(rename = "COLOR_TRANSFER_CHARACTERISTICS_SMPTEST2084")]
+    SMPTEST2084,
+    /// Hybrid Log-Gamma (HLG).
+    #[serde(rename = "COLOR_TRANSFER_CHARACTERISTICS_ARIB_STD_B67")]
+    AribStdB67,
+}
+
+impl TransferCharacteristics {
+    /// Whether or not the transfer characteristics are used for HDR content (PQ or HLG).
+    #[inline]
+    pub fn is_hdr(&self) -> bool {
+        matches!(self, Self::SMPTEST2084 | Self::AribStdB67)
+    }
 }
 
 #[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
 pub enum MatrixCoefficients {
     #[serde(rename = "COLOR_MATRIX_COEFFICIENTS_BT709")]
-    BT709
+    BT709,
+    #[serde(rename = "COLOR_MATRIX_COEFFICIENTS_BT2020_NCL")]
+    BT2020Ncl,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -111,7 +173,30 @@ pub struct MimeType {
 #[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum ProjectionType {
+    /// A regular, flat video.
     Rectangular,
+    /// A 360° video, projected onto a rectangle.
+    Equirectangular,
+    /// A stereoscopic 360° video, with the frames of both eyes stacked on top of each other.
+    #[serde(rename = "EQUIRECTANGULAR_THREED_TOP_BOTTOM")]
+    EquirectangularThreedTopBottom,
+    /// A VR video, i.e. VR180, which is projected onto a custom mesh.
+    Mesh,
+}
+
+impl ProjectionType {
+    /// Whether or not the projection is used for 360° videos.
+    #[inline]
+    pub fn is_360(&self) -> bool {
+        matches!(self, Self::Equirectangular | Self::EquirectangularThreedTopBottom)
+    }
+
+    /// Whether or not the projection is used for VR content, i.e. 360° videos, or VR180 videos.
+    /// Only [`ProjectionType::Rectangular`] videos can be played by regular video players.
+    #[inline]
+    pub fn is_vr(&self) -> bool {
+        *self != Self::Rectangular
+    }
 }
 
 #[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -36,6 +36,16 @@ pub struct VideoDetails {
     pub view_count: u64,
 }
 
+impl VideoDetails {
+    /// The thumbnail with the highest resolution, if the video has any.
+    #[inline]
+    pub fn best_thumbnail(&self) -> Option<&Thumbnail> {
+        self.thumbnails
+            .iter()
+            .max_by_key(|thumbnail| thumbnail.width * thumbnail.height)
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
 pub struct Thumbnail {
     pub width: u64,
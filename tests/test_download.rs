@@ -218,7 +218,7 @@ async fn download_to() {
     let id = random_id(SIGNATURE_CIPHER);
     let path = download_path_from_id(id.as_borrowed()).await;
 
-    let _: () = dbg!(Video::from_id(id)
+    let _: u64 = dbg!(Video::from_id(id)
         .await
         .unwrap())
         .worst_quality()
@@ -0,0 +1,1167 @@
+// The mock streams are built using `Stream::from_info`, which requires the `serde` feature.
+#![cfg(all(feature = "download", feature = "serde"))]
+
+use std::sync::{Arc, Mutex};
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+use rustube::{Id, Stream, StreamInfo, VideoDetails};
+use rustube::url::Url;
+use rustube::video_info::player_response::streaming_data::{FormatType, ProjectionType, Quality, SignatureCipher};
+
+/// The segments the mock server serves, indexed by their `sq`.
+/// The 0th segment announces the number of following segments in its body.
+const COUNTED_SEGMENTS: &[&[u8]] = &[
+    b"Segment-Count: 3\r\n",
+    b"first ",
+    b"second ",
+    b"third",
+];
+/// Like [`COUNTED_SEGMENTS`], but without a `Segment-Count`, so the end of the stream has to be
+/// detected by requesting segments until the server returns a 404.
+const UNCOUNTED_SEGMENTS: &[&[u8]] = &[
+    b"header ",
+    b"first ",
+    b"second ",
+    b"third",
+];
+
+/// Starts a minimal HTTP server, that behaves like YouTube does for sequenced streams:
+/// Requests without a `sq` query parameter are answered with `404 Not Found`.
+///
+/// Returns the base url of the server, and the list of requested paths.
+async fn sequenced_server(segments: &'static [&'static [u8]]) -> (Url, Arc<Mutex<Vec<String>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = Url::parse(&format!("http://{}/videoplayback?id=1", listener.local_addr().unwrap())).unwrap();
+    let requests = Arc::new(Mutex::new(Vec::new()));
+
+    let requests_ = Arc::clone(&requests);
+    tokio::spawn(async move {
+        loop {
+            let (mut socket, _) = listener.accept().await.unwrap();
+
+            let mut buf = Vec::new();
+            while !buf.ends_with(b"\r\n\r\n") {
+                let mut byte = [0];
+                if socket.read(&mut byte).await.unwrap() == 0 { break; }
+                buf.push(byte[0]);
+            }
+
+            let request = String::from_utf8(buf).unwrap();
+            let path = request.split(' ').nth(1).unwrap().to_owned();
+            let segment = Url::parse("http://localhost")
+                .unwrap()
+                .join(&path)
+                .unwrap()
+                .query_pairs()
+                .find(|(k, _)| k == "sq")
+                .and_then(|(_, sq)| segments.get(sq.parse::<usize>().unwrap()).copied());
+            requests_.lock().unwrap().push(path);
+
+            let response = match segment {
+                Some(body) => [
+                    format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n", body.len()).as_bytes(),
+                    body
+                ].concat(),
+                None => b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_vec(),
+            };
+            socket.write_all(&response).await.unwrap();
+            socket.shutdown().await.unwrap();
+        }
+    });
+
+    (url, requests)
+}
+
+fn sequenced_stream(url: Url) -> Stream {
+    sequenced_stream_with_content_length(url, None)
+}
+
+/// A [`Stream`], which is served in sequenced segments, like [`sequenced_stream`].
+/// If `content_length` is `Some`, the [`Stream`] is treated as a regular, non-OTF stream, whose
+/// content length is known upfront.
+fn sequenced_stream_with_content_length(url: Url, content_length: Option<u64>) -> Stream {
+    let video_details = VideoDetails {
+        allow_ratings: false,
+        author: String::new(),
+        average_rating: 0.,
+        channel_id: String::new(),
+        is_crawlable: false,
+        is_live_content: false,
+        is_live_default_broadcast: false,
+        is_low_latency_live_stream: false,
+        is_owner_viewing: false,
+        is_private: false,
+        is_unplugged_corpus: false,
+        key_words: vec![],
+        length_seconds: 0,
+        short_description: String::new(),
+        thumbnails: vec![],
+        title: String::new(),
+        video_id: Id::from_str("sequenced00").unwrap(),
+        view_count: 0,
+    };
+
+    let info = StreamInfo {
+        mime: "video/mp4".parse().unwrap(),
+        codecs: vec!["avc1.4d401e".to_owned()],
+        is_progressive: false,
+        includes_video_track: true,
+        includes_audio_track: false,
+        format_type: content_length.map_or(Some(FormatType::Otf), |_| None),
+        approx_duration_ms: None,
+        audio_channels: None,
+        audio_quality: None,
+        audio_sample_rate: None,
+        average_bitrate: None,
+        bitrate: None,
+        color_info: None,
+        content_length,
+        fps: 30,
+        height: None,
+        high_replication: None,
+        index_range: None,
+        init_range: None,
+        is_otf: content_length.is_none(),
+        itag: 0,
+        last_modified: chrono::Utc::now(),
+        loudness_db: None,
+        projection_type: ProjectionType::Rectangular,
+        quality: Quality::Tiny,
+        quality_label: None,
+        signature_cipher: SignatureCipher { url, s: None, sp: None },
+        width: None,
+    };
+
+    Stream::from_info(info, reqwest::Client::new(), Arc::new(video_details))
+}
+
+#[tokio::test]
+async fn download_sequenced() {
+    let (url, requests) = sequenced_server(COUNTED_SEGMENTS).await;
+    let stream = sequenced_stream(url);
+
+    let path = std::env::temp_dir().join("rustube_sequenced.mp4");
+    let written = stream
+        .download_to(&path)
+        .await
+        .unwrap();
+
+    let expected = COUNTED_SEGMENTS.concat();
+    assert_eq!(written, expected.len() as u64);
+    assert_eq!(tokio::fs::read(&path).await.unwrap(), expected);
+    // the temporary file was renamed to the final path
+    assert!(!path.with_extension(format!("mp4.{}.part", std::process::id())).exists());
+    tokio::fs::remove_file(&path).await.unwrap();
+
+    // all segments, including the last one, have to be requested
+    // (segments may be requested concurrently, so only the written file has to be in order)
+    let mut requests = requests.lock().unwrap().clone();
+    requests.sort();
+    assert_eq!(
+        requests,
+        [
+            "/videoplayback?id=1",
+            "/videoplayback?id=1&sq=0",
+            "/videoplayback?id=1&sq=1",
+            "/videoplayback?id=1&sq=2",
+            "/videoplayback?id=1&sq=3",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn download_sequenced_without_segment_count() {
+    let (url, requests) = sequenced_server(UNCOUNTED_SEGMENTS).await;
+    let stream = sequenced_stream(url);
+
+    let path = std::env::temp_dir().join("rustube_sequenced_without_segment_count.mp4");
+    let written = stream
+        .download_to(&path)
+        .await
+        .unwrap();
+
+    let expected = UNCOUNTED_SEGMENTS.concat();
+    assert_eq!(written, expected.len() as u64);
+    assert_eq!(tokio::fs::read(&path).await.unwrap(), expected);
+    tokio::fs::remove_file(&path).await.unwrap();
+
+    // segments are requested until the server responds with a 404
+    assert_eq!(
+        *requests.lock().unwrap(),
+        [
+            "/videoplayback?id=1",
+            "/videoplayback?id=1&sq=0",
+            "/videoplayback?id=1&sq=1",
+            "/videoplayback?id=1&sq=2",
+            "/videoplayback?id=1&sq=3",
+            "/videoplayback?id=1&sq=4",
+        ]
+    );
+}
+
+#[cfg(feature = "callback")]
+#[tokio::test]
+async fn download_sequenced_reports_final_progress() {
+    use rustube::Callback;
+
+    let (url, _) = sequenced_server(COUNTED_SEGMENTS).await;
+    let stream = sequenced_stream(url);
+
+    let progress = Arc::new(Mutex::new(Vec::new()));
+    let progress_ = Arc::clone(&progress);
+    let callback = Callback::new()
+        .connect_on_progress_closure_slow(move |args| progress_.lock().unwrap().push(args.current_chunk));
+
+    let path = std::env::temp_dir().join("rustube_sequenced_callback.mp4");
+    let written = stream
+        .download_to_callback(&path, callback)
+        .await
+        .unwrap();
+    tokio::fs::remove_file(&path).await.unwrap();
+
+    // the download is way smaller than a MB, but the slow handler still sees the final progress
+    assert_eq!(progress.lock().unwrap().last(), Some(&written));
+}
+
+#[cfg(feature = "callback")]
+#[tokio::test]
+async fn download_sequenced_cloned_callback() {
+    use rustube::Callback;
+
+    let (url, _) = sequenced_server(COUNTED_SEGMENTS).await;
+    let stream = sequenced_stream(url);
+
+    let completed = Arc::new(Mutex::new(0));
+    let completed_ = Arc::clone(&completed);
+    let template = Callback::new()
+        .connect_on_complete_closure(move |_| *completed_.lock().unwrap() += 1);
+
+    let path = std::env::temp_dir().join("rustube_sequenced_cloned_callback.mp4");
+    for _ in 0..2 {
+        stream
+            .download_to_callback(&path, template.clone())
+            .await
+            .unwrap();
+    }
+    tokio::fs::remove_file(&path).await.unwrap();
+
+    assert_eq!(*completed.lock().unwrap(), 2);
+}
+
+#[cfg(feature = "callback")]
+#[tokio::test]
+async fn download_sequenced_progress_events() {
+    use rustube::ProgressEvent;
+    use tokio_stream::StreamExt;
+
+    let (url, _) = sequenced_server(COUNTED_SEGMENTS).await;
+    let stream = sequenced_stream(url);
+
+    let path = std::env::temp_dir().join("rustube_sequenced_progress_events.mp4");
+    let events = stream.download_to_progress(&path);
+    tokio::pin!(events);
+
+    let mut progress = Vec::new();
+    let mut completed = None;
+    while let Some(event) = events.next().await {
+        assert!(completed.is_none(), "received an event after ProgressEvent::Completed");
+        match event {
+            ProgressEvent::Progress(args) => progress.push(args.current_chunk),
+            ProgressEvent::Completed(result) => completed = Some(result.unwrap()),
+        }
+    }
+    tokio::fs::remove_file(&path).await.unwrap();
+
+    let written = completed.expect("the last event has to be ProgressEvent::Completed");
+    assert_eq!(progress.last(), Some(&written));
+}
+
+#[tokio::test]
+async fn download_sequenced_to_many() {
+    let (url, requests) = sequenced_server(COUNTED_SEGMENTS).await;
+    let stream = sequenced_stream(url);
+
+    let paths = [
+        std::env::temp_dir().join("rustube_sequenced_to_many_0.mp4"),
+        std::env::temp_dir().join("rustube_sequenced_to_many_1.mp4"),
+    ];
+    let written = stream
+        .download_to_many(&paths)
+        .await
+        .unwrap();
+
+    let expected = COUNTED_SEGMENTS.concat();
+    assert_eq!(written, expected.len() as u64);
+    for path in paths.iter() {
+        assert_eq!(tokio::fs::read(path).await.unwrap(), expected);
+        tokio::fs::remove_file(path).await.unwrap();
+    }
+
+    // the stream is only fetched once
+    assert_eq!(requests.lock().unwrap().len(), COUNTED_SEGMENTS.len() + 1);
+}
+
+#[tokio::test]
+async fn download_to_many_removes_moved_files_on_error() {
+    let (url, _) = sequenced_server(COUNTED_SEGMENTS).await;
+    let stream = sequenced_stream(url);
+
+    // a file can't replace a non-empty directory, so moving the second file fails
+    let dir = std::env::temp_dir().join("rustube_to_many_error");
+    let paths = [dir.join("0.mp4"), dir.join("1.mp4")];
+    tokio::fs::create_dir_all(paths[1].join("occupied")).await.unwrap();
+
+    assert!(stream.download_to_many(&paths).await.is_err());
+    assert!(!paths[0].exists());
+    assert!(std::fs::read_dir(&dir).unwrap().all(|entry| entry.unwrap().path() == paths[1]));
+
+    tokio::fs::remove_dir_all(&dir).await.unwrap();
+}
+
+#[tokio::test]
+async fn download_sequenced_bytes() {
+    let (url, _) = sequenced_server(COUNTED_SEGMENTS).await;
+    let stream = sequenced_stream(url);
+
+    let bytes = stream
+        .download_bytes()
+        .await
+        .unwrap();
+
+    assert_eq!(bytes, COUNTED_SEGMENTS.concat());
+}
+
+#[tokio::test]
+async fn download_sequenced_bytes_too_large() {
+    let (url, _) = sequenced_server(COUNTED_SEGMENTS).await;
+    let stream = sequenced_stream(url);
+
+    let max_size = COUNTED_SEGMENTS.concat().len() as u64 - 1;
+    let err = stream
+        .download_bytes_with_limit(max_size)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, rustube::Error::TooLarge { max_size: m } if m == max_size));
+}
+
+#[tokio::test]
+async fn download_sequenced_segments() {
+    let (url, requests) = sequenced_server(COUNTED_SEGMENTS).await;
+    let stream = sequenced_stream(url);
+
+    let path = std::env::temp_dir().join("rustube_sequenced_segments.mp4");
+    let written = stream
+        .download_segments_to(&path, 2)
+        .await
+        .unwrap();
+
+    let expected = COUNTED_SEGMENTS[..3].concat();
+    assert_eq!(written, expected.len() as u64);
+    assert_eq!(tokio::fs::read(&path).await.unwrap(), expected);
+    tokio::fs::remove_file(&path).await.unwrap();
+
+    // the third segment is never requested
+    assert!(requests.lock().unwrap().iter().all(|request| !request.contains("sq=3")));
+}
+
+#[tokio::test]
+async fn download_without_sequenced_fallback() {
+    let (url, requests) = sequenced_server(COUNTED_SEGMENTS).await;
+    let stream = sequenced_stream(url).with_sequenced_fallback(false);
+
+    let err = stream
+        .download_bytes()
+        .await
+        .unwrap_err();
+
+    match err {
+        rustube::Error::Request(e) => assert_eq!(e.status(), Some(reqwest::StatusCode::NOT_FOUND)),
+        e => panic!("unexpected error: {:?}", e),
+    }
+    assert_eq!(requests.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn download_sequenced_with_options() {
+    use rustube::DownloadOptions;
+
+    let (url, _) = sequenced_server(COUNTED_SEGMENTS).await;
+    let stream = sequenced_stream(url);
+
+    let expected = COUNTED_SEGMENTS.concat();
+    let options = DownloadOptions::new()
+        .to_dir(std::env::temp_dir())
+        .throttle(expected.len() as u64 * 4);
+    let start = std::time::Instant::now();
+    let path = stream
+        .download_with(options)
+        .await
+        .unwrap();
+
+    assert_eq!(path, std::env::temp_dir().join("sequenced00.f0.mp4"));
+    assert_eq!(tokio::fs::read(&path).await.unwrap(), expected);
+    tokio::fs::remove_file(&path).await.unwrap();
+    // at a quarter of the size per second, the last segment is written after ~0.2s
+    assert!(start.elapsed() >= std::time::Duration::from_millis(150));
+}
+
+#[tokio::test]
+async fn download_sequenced_chunks() {
+    let (url, _) = sequenced_server(COUNTED_SEGMENTS).await;
+    let stream = sequenced_stream(url);
+
+    let mut chunks = stream.download_chunks(1);
+    let mut received = Vec::new();
+    while let Some(chunk) = chunks.recv().await {
+        received.push(chunk.unwrap());
+    }
+
+    // every segment is forwarded as it was received
+    assert_eq!(received, COUNTED_SEGMENTS);
+}
+
+#[tokio::test]
+async fn download_sequenced_with_sidecar() {
+    use rustube::DownloadOptions;
+
+    let (url, _) = sequenced_server(COUNTED_SEGMENTS).await;
+    let stream = sequenced_stream(url);
+
+    let path = std::env::temp_dir().join("rustube_sequenced_sidecar.mp4");
+    let options = DownloadOptions::new()
+        .to_path(&path)
+        .sidecar(true);
+    stream
+        .download_with(options)
+        .await
+        .unwrap();
+
+    let sidecar_path = std::env::temp_dir().join("rustube_sequenced_sidecar.mp4.json");
+    let sidecar: serde_json::Value = serde_json::from_slice(&tokio::fs::read(&sidecar_path).await.unwrap()).unwrap();
+    assert_eq!(sidecar["stream"]["itag"], 0);
+    assert_eq!(sidecar["videoDetails"]["videoId"], "sequenced00");
+
+    tokio::fs::remove_file(&path).await.unwrap();
+    tokio::fs::remove_file(&sidecar_path).await.unwrap();
+}
+
+#[tokio::test]
+async fn download_sequenced_exceeding_max_bytes() {
+    use rustube::DownloadOptions;
+
+    let (url, _) = sequenced_server(COUNTED_SEGMENTS).await;
+    let stream = sequenced_stream(url);
+
+    let path = std::env::temp_dir().join("rustube_sequenced_max_bytes.mp4");
+    let options = DownloadOptions::new()
+        .to_path(&path)
+        .max_bytes(20);
+    let err = stream
+        .download_with(options)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, rustube::Error::SizeLimitExceeded { limit: 20 }));
+    assert!(!path.exists());
+    assert!(!path.with_extension(format!("mp4.{}.part", std::process::id())).exists());
+}
+
+#[tokio::test]
+async fn download_sequenced_resumes_from_segment() {
+    let (url, requests) = sequenced_server(COUNTED_SEGMENTS).await;
+    let stream = sequenced_stream(url);
+
+    // simulate a download, that was interrupted while writing the second segment
+    let path = std::env::temp_dir().join("rustube_sequenced_resumable.mp4");
+    let manifest = std::env::temp_dir().join("rustube_sequenced_resumable.mp4.seq");
+    let written = COUNTED_SEGMENTS[..2].concat();
+    tokio::fs::write(&path, [written.as_slice(), b"sec"].concat()).await.unwrap();
+    tokio::fs::write(&manifest, format!("1 {}", written.len())).await.unwrap();
+
+    let bytes = stream
+        .download_seq_resumable_to(&path)
+        .await
+        .unwrap();
+
+    let expected = COUNTED_SEGMENTS.concat();
+    assert_eq!(bytes, expected.len() as u64);
+    assert_eq!(tokio::fs::read(&path).await.unwrap(), expected);
+    assert!(!manifest.exists());
+    tokio::fs::remove_file(&path).await.unwrap();
+
+    // the already written first segment is not requested again
+    assert!(requests.lock().unwrap().iter().all(|request| !request.contains("sq=1")));
+}
+
+#[cfg(feature = "callback")]
+#[tokio::test]
+async fn download_sequenced_progress_events_end_after_error() {
+    use rustube::ProgressEvent;
+    use tokio_stream::StreamExt;
+
+    // every request is answered with a 404, so the download fails right away
+    let (url, _) = sequenced_server(&[]).await;
+    let stream = sequenced_stream(url);
+
+    let path = std::env::temp_dir().join("rustube_sequenced_progress_events_error.mp4");
+    let events = stream.download_to_progress(&path);
+    tokio::pin!(events);
+
+    match events.next().await {
+        Some(ProgressEvent::Completed(Err(_))) => {}
+        event => panic!("expected a failed ProgressEvent::Completed, got {:?}", event),
+    }
+    assert!(events.next().await.is_none());
+    // the stream is fused, so it can be polled after it ended
+    assert!(events.next().await.is_none());
+    assert!(!path.exists());
+}
+
+#[tokio::test]
+async fn download_incomplete_is_retried() {
+    use rustube::DownloadOptions;
+
+    let (url, requests) = sequenced_server(COUNTED_SEGMENTS).await;
+    // the server serves fewer bytes than announced, as if the CDN truncated the response
+    let expected = COUNTED_SEGMENTS.concat().len() as u64 + 5;
+    let stream = sequenced_stream_with_content_length(url, Some(expected));
+
+    let path = std::env::temp_dir().join("rustube_incomplete.mp4");
+    let err = stream
+        .download_with(DownloadOptions::new().to_path(&path).integrity_retries(2))
+        .await
+        .unwrap_err();
+
+    match err {
+        rustube::Error::Incomplete { expected: e, actual } => {
+            assert_eq!(e, expected);
+            assert_eq!(actual, expected - 5);
+        }
+        e => panic!("unexpected error: {:?}", e),
+    }
+    assert!(!path.exists());
+    // every attempt requests the resource itself, and the four segments
+    assert_eq!(requests.lock().unwrap().len(), 3 * 5);
+}
+
+#[tokio::test]
+async fn download_with_overwrite_policy() {
+    use rustube::{DownloadOptions, OverwritePolicy};
+
+    let (url, requests) = sequenced_server(COUNTED_SEGMENTS).await;
+    let stream = sequenced_stream(url);
+
+    let path = std::env::temp_dir().join("rustube_overwrite_policy.mp4");
+    tokio::fs::write(&path, b"existing").await.unwrap();
+
+    let skipped = stream
+        .download_with(DownloadOptions::new().to_path(&path).overwrite(OverwritePolicy::Skip))
+        .await
+        .unwrap();
+    assert_eq!(skipped, path);
+    assert_eq!(tokio::fs::read(&path).await.unwrap(), b"existing");
+
+    let err = stream
+        .download_with(DownloadOptions::new().to_path(&path).overwrite(OverwritePolicy::Error))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, rustube::Error::FileExists(ref p) if p == &path));
+    assert!(requests.lock().unwrap().is_empty());
+
+    // without a manifest of a previous sequenced download, resuming starts over
+    stream
+        .download_with(DownloadOptions::new().to_path(&path).overwrite(OverwritePolicy::Resume))
+        .await
+        .unwrap();
+    assert_eq!(tokio::fs::read(&path).await.unwrap(), COUNTED_SEGMENTS.concat());
+    tokio::fs::remove_file(&path).await.unwrap();
+}
+
+#[tokio::test]
+async fn download_resumed_after_interruption() {
+    use rustube::{DownloadOptions, OverwritePolicy};
+
+    // the server misses the last segment, so the first download fails after the second one
+    let (url, _) = sequenced_server(&[b"Segment-Count: 3\r\n", b"first ", b"second "]).await;
+    let interrupted = sequenced_stream(url);
+    let (url, requests) = sequenced_server(COUNTED_SEGMENTS).await;
+    let stream = sequenced_stream(url);
+
+    let path = std::env::temp_dir().join("rustube_resumed_after_interruption.mp4");
+    let options = DownloadOptions::new()
+        .to_path(&path)
+        .overwrite(OverwritePolicy::Resume);
+    interrupted
+        .download_with(options.clone())
+        .await
+        .unwrap_err();
+    // the file is kept, so the download can be resumed
+    assert_eq!(tokio::fs::read(&path).await.unwrap(), COUNTED_SEGMENTS[..3].concat());
+
+    stream
+        .download_with(options)
+        .await
+        .unwrap();
+    assert_eq!(tokio::fs::read(&path).await.unwrap(), COUNTED_SEGMENTS.concat());
+    assert!(!path.with_extension("mp4.seq").exists());
+    tokio::fs::remove_file(&path).await.unwrap();
+
+    // only the missing segment is requested
+    let requests = requests.lock().unwrap();
+    assert!(requests.iter().all(|request| !request.contains("sq=1") && !request.contains("sq=2")));
+    assert!(requests.iter().any(|request| request.contains("sq=3")));
+}
+
+#[tokio::test]
+async fn download_resumed_with_max_bytes() {
+    use rustube::{DownloadOptions, OverwritePolicy};
+
+    let (url, _requests) = sequenced_server(COUNTED_SEGMENTS).await;
+    let stream = sequenced_stream(url);
+
+    let path = std::env::temp_dir().join("rustube_resumed_max_bytes.mp4");
+    tokio::fs::write(&path, b"existing").await.unwrap();
+
+    let err = stream
+        .download_with(
+            DownloadOptions::new()
+                .to_path(&path)
+                .overwrite(OverwritePolicy::Resume)
+                .max_bytes(1)
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, rustube::Error::SizeLimitExceeded { limit: 1 }));
+    // a resumed download keeps its file, so it can be resumed again
+    assert!(tokio::fs::metadata(&path).await.is_ok());
+    tokio::fs::remove_file(&path).await.unwrap();
+}
+
+#[tokio::test]
+async fn download_empty_stream() {
+    let (url, _) = sequenced_server(&[b""]).await;
+    let stream = sequenced_stream(url);
+
+    let path = std::env::temp_dir().join("rustube_empty_stream.mp4");
+    let err = stream
+        .download_to(&path)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, rustube::Error::EmptyStream), "unexpected error: {:?}", err);
+    assert!(!path.exists());
+}
+
+#[tokio::test]
+async fn download_sequenced_with_read_buffer_size() {
+    use rustube::DownloadOptions;
+
+    let (url, _) = sequenced_server(UNCOUNTED_SEGMENTS).await;
+    let stream = sequenced_stream(url);
+
+    let path = std::env::temp_dir().join("rustube_sequenced_read_buffer_size.mp4");
+    stream
+        .download_with(DownloadOptions::new().to_path(&path).read_buffer_size(1024))
+        .await
+        .unwrap();
+
+    assert_eq!(tokio::fs::read(&path).await.unwrap(), UNCOUNTED_SEGMENTS.concat());
+    tokio::fs::remove_file(&path).await.unwrap();
+}
+
+#[cfg(feature = "callback")]
+#[tokio::test]
+async fn download_sequenced_pair_callback() {
+    use rustube::Callback;
+
+    let (video_url, _) = sequenced_server(COUNTED_SEGMENTS).await;
+    let (audio_url, _) = sequenced_server(UNCOUNTED_SEGMENTS).await;
+    let video = sequenced_stream(video_url);
+    let mut audio = sequenced_stream(audio_url);
+    audio.itag = 1;
+
+    let progress = Arc::new(Mutex::new(Vec::new()));
+    let progress_ = Arc::clone(&progress);
+    let completed = Arc::new(Mutex::new(Vec::new()));
+    let completed_ = Arc::clone(&completed);
+    let callback = Callback::new()
+        .connect_on_progress_closure(move |args| progress_.lock().unwrap().push(args.current_chunk))
+        .connect_on_complete_closure(move |path| completed_.lock().unwrap().push(path));
+
+    let (video_path, audio_path) = Stream::download_pair_callback(&video, &audio, callback)
+        .await
+        .unwrap();
+
+    assert_eq!(tokio::fs::read(&video_path).await.unwrap(), COUNTED_SEGMENTS.concat());
+    assert_eq!(tokio::fs::read(&audio_path).await.unwrap(), UNCOUNTED_SEGMENTS.concat());
+    tokio::fs::remove_file(&video_path).await.unwrap();
+    tokio::fs::remove_file(&audio_path).await.unwrap();
+
+    let total = (COUNTED_SEGMENTS.concat().len() + UNCOUNTED_SEGMENTS.concat().len()) as u64;
+    let progress = progress.lock().unwrap();
+    assert_eq!(progress.last(), Some(&total));
+    assert!(progress.windows(2).all(|w| w[0] < w[1]));
+    assert_eq!(*completed.lock().unwrap(), vec![Some(video_path)]);
+}
+
+#[tokio::test]
+async fn download_sequenced_with_observer() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use rustube::DownloadObserver;
+
+    #[derive(Default)]
+    struct Metrics {
+        requests: AtomicUsize,
+        bytes: AtomicUsize,
+        finished: Mutex<Vec<u64>>,
+    }
+
+    impl DownloadObserver for Metrics {
+        fn on_request_start(&self, _stream: &Stream, _request: &reqwest::Request) {
+            self.requests.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn on_bytes(&self, _stream: &Stream, bytes: usize) {
+            self.bytes.fetch_add(bytes, Ordering::Relaxed);
+        }
+
+        fn on_finish(&self, _stream: &Stream, result: Result<u64, &rustube::Error>, _duration: std::time::Duration) {
+            self.finished.lock().unwrap().push(result.unwrap());
+        }
+    }
+
+    let (url, requests) = sequenced_server(COUNTED_SEGMENTS).await;
+    let metrics = Arc::new(Metrics::default());
+    let stream = sequenced_stream(url).with_observer(Arc::clone(&metrics) as Arc<dyn DownloadObserver>);
+
+    let path = std::env::temp_dir().join("rustube_sequenced_observer.mp4");
+    let written = stream.download_to(&path).await.unwrap();
+    tokio::fs::remove_file(&path).await.unwrap();
+
+    assert_eq!(metrics.requests.load(Ordering::Relaxed), requests.lock().unwrap().len());
+    assert_eq!(metrics.bytes.load(Ordering::Relaxed), written as usize);
+    assert_eq!(*metrics.finished.lock().unwrap(), vec![written]);
+}
+
+#[tokio::test]
+async fn download_sequenced_from_fallback_url() {
+    let (url, requests) = sequenced_server(COUNTED_SEGMENTS).await;
+
+    // nothing listens on the port of the primary url anymore, so connecting fails
+    let unreachable = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let primary = Url::parse(&format!("http://{}/videoplayback?id=1", unreachable.local_addr().unwrap())).unwrap();
+    drop(unreachable);
+    let stream = sequenced_stream(primary).with_fallback_urls(vec![url]);
+
+    let bytes = stream.download_bytes().await.unwrap();
+
+    assert_eq!(bytes, COUNTED_SEGMENTS.concat());
+    // every segment was requested from the fallback url
+    assert!(requests.lock().unwrap().iter().any(|request| request.contains("sq=3")));
+}
+
+#[cfg(feature = "callback")]
+#[tokio::test]
+async fn download_sequenced_with_progress_interval() {
+    use rustube::Callback;
+
+    let (url, _) = sequenced_server(COUNTED_SEGMENTS).await;
+    let stream = sequenced_stream(url);
+
+    let progress = Arc::new(Mutex::new(Vec::new()));
+    let progress_ = Arc::clone(&progress);
+    let callback = Callback::new()
+        .with_progress_interval(std::time::Duration::from_secs(3600))
+        .connect_on_progress_closure(move |args| progress_.lock().unwrap().push(args.current_chunk));
+
+    let path = std::env::temp_dir().join("rustube_sequenced_progress_interval.mp4");
+    let written = stream
+        .download_to_callback(&path, callback)
+        .await
+        .unwrap();
+    tokio::fs::remove_file(&path).await.unwrap();
+
+    // only the first update is reported right away, everything else is coalesced into the final one
+    let progress = progress.lock().unwrap();
+    assert_eq!(progress.len(), 2);
+    assert_eq!(progress.last(), Some(&written));
+}
+
+#[tokio::test]
+async fn download_sequenced_with_temp_dir() {
+    use rustube::DownloadOptions;
+
+    let (url, _) = sequenced_server(COUNTED_SEGMENTS).await;
+    let stream = sequenced_stream(url);
+
+    let temp_dir = std::env::temp_dir().join("rustube_temp_dir");
+    tokio::fs::create_dir_all(&temp_dir).await.unwrap();
+    let path = std::env::temp_dir().join("rustube_sequenced_temp_dir.mp4");
+    stream
+        .download_with(DownloadOptions::new().to_path(&path).temp_dir(&temp_dir))
+        .await
+        .unwrap();
+
+    assert_eq!(tokio::fs::read(&path).await.unwrap(), COUNTED_SEGMENTS.concat());
+    tokio::fs::remove_file(&path).await.unwrap();
+    // the part file was moved out of the temp dir
+    assert!(std::fs::read_dir(&temp_dir).unwrap().next().is_none());
+    tokio::fs::remove_dir(&temp_dir).await.unwrap();
+}
+
+#[tokio::test]
+async fn download_sequenced_clone_with_client() {
+    use rustube::ProxyPool;
+
+    let (url, _) = sequenced_server(COUNTED_SEGMENTS).await;
+    // the proxy cannot be reached, so only a clone without the pool can download the stream
+    let proxy_pool = ProxyPool::new(["http://127.0.0.1:1"]).unwrap();
+    let stream = sequenced_stream(url).with_proxy_pool(Arc::new(proxy_pool));
+    let stream = stream.clone_with_client(reqwest::Client::new());
+
+    let bytes = stream.download_bytes().await.unwrap();
+    assert_eq!(bytes, COUNTED_SEGMENTS.concat());
+}
+
+#[tokio::test]
+async fn download_sequenced_with_deadline() {
+    use rustube::DownloadOptions;
+
+    let (url, _) = sequenced_server(COUNTED_SEGMENTS).await;
+    let stream = sequenced_stream(url);
+
+    let path = std::env::temp_dir().join("rustube_sequenced_deadline.mp4");
+    let options = DownloadOptions::new()
+        .to_path(&path)
+        .deadline(std::time::Instant::now())
+        .retries(3);
+    let result = stream.download_with(options).await;
+
+    assert!(matches!(result, Err(rustube::Error::DeadlineExceeded)));
+    assert!(!path.exists());
+    assert!(!path.with_extension(format!("mp4.{}.part", std::process::id())).exists());
+}
+
+#[tokio::test]
+async fn is_available() {
+    let (mut url, _) = sequenced_server(COUNTED_SEGMENTS).await;
+    // without a sequence number, the server responds with 404
+    assert!(!sequenced_stream(url.clone()).is_available().await);
+
+    url.query_pairs_mut().append_pair("sq", "0");
+    assert!(sequenced_stream(url).is_available().await);
+
+    let unreachable = Url::parse("http://127.0.0.1:1/videoplayback").unwrap();
+    assert!(!sequenced_stream(unreachable).is_available().await);
+}
+
+#[tokio::test]
+async fn download_sequenced_with_opener() {
+    use std::io::Write;
+
+    let (url, _) = sequenced_server(COUNTED_SEGMENTS).await;
+    let stream = sequenced_stream(url);
+
+    let path = std::env::temp_dir().join("rustube_sequenced_opener.mp4");
+    let opened = Arc::new(Mutex::new(None));
+    let opened_ = Arc::clone(&opened);
+    let written = stream
+        .download_to_with_opener(&path, move |part_path| {
+            *opened_.lock().unwrap() = Some(part_path.to_owned());
+            let mut file = std::fs::File::create(part_path)?;
+            file.write_all(b"header")?;
+            Ok(tokio::fs::File::from_std(file))
+        })
+        .await
+        .unwrap();
+
+    let expected = COUNTED_SEGMENTS.concat();
+    assert_eq!(written, expected.len() as u64);
+    assert_eq!(tokio::fs::read(&path).await.unwrap(), [&b"header"[..], &expected].concat());
+    // the writer was opened at the temporary path, which was renamed afterwards
+    let part_path = path.with_extension(format!("mp4.{}.part", std::process::id()));
+    assert_eq!(*opened.lock().unwrap(), Some(part_path.clone()));
+    assert!(!part_path.exists());
+    tokio::fs::remove_file(&path).await.unwrap();
+}
+
+/// A server, which redirects every request to itself, and counts the requests.
+async fn redirect_loop_server() -> (Url, Arc<Mutex<usize>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = Url::parse(&format!("http://{}/videoplayback?id=1", listener.local_addr().unwrap())).unwrap();
+    let requests = Arc::new(Mutex::new(0));
+
+    let (url_, requests_) = (url.clone(), Arc::clone(&requests));
+    tokio::spawn(async move {
+        loop {
+            let (mut socket, _) = listener.accept().await.unwrap();
+
+            let mut buf = Vec::new();
+            while !buf.ends_with(b"\r\n\r\n") {
+                let mut byte = [0];
+                if socket.read(&mut byte).await.unwrap() == 0 { break; }
+                buf.push(byte[0]);
+            }
+            *requests_.lock().unwrap() += 1;
+
+            let response = format!(
+                "HTTP/1.1 302 Found\r\nLocation: {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                url_
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+            socket.shutdown().await.unwrap();
+        }
+    });
+
+    (url, requests)
+}
+
+#[tokio::test]
+async fn download_redirect_loop() {
+    use rustube::DownloadOptions;
+
+    let (url, requests) = redirect_loop_server().await;
+    let stream = sequenced_stream_with_content_length(url, Some(10));
+
+    let err = stream.download_bytes().await.unwrap_err();
+    assert!(matches!(err, rustube::Error::TooManyRedirects(Some(_))));
+
+    // without following redirects, the first redirect is an error
+    *requests.lock().unwrap() = 0;
+    let path = std::env::temp_dir().join("rustube_redirect_loop.mp4");
+    let client = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .unwrap();
+    let options = DownloadOptions::new().to_path(&path).client(client.clone());
+    let err = stream.download_with(options).await.unwrap_err();
+    assert!(matches!(err, rustube::Error::TooManyRedirects(Some(_))));
+    assert_eq!(*requests.lock().unwrap(), 1);
+    assert!(!path.exists());
+
+    // the redirect policy of a custom client cannot be changed
+    *requests.lock().unwrap() = 0;
+    let options = DownloadOptions::new().to_path(&path).client(client).max_redirects(0);
+    let err = stream.download_with(options).await.unwrap_err();
+    assert!(matches!(err, rustube::Error::Custom(_)));
+    let options = DownloadOptions::new().to_path(&path).max_redirects(0);
+    let err = stream.download_with(options).await.unwrap_err();
+    assert!(matches!(err, rustube::Error::Custom(_)));
+    assert_eq!(*requests.lock().unwrap(), 0);
+}
+
+#[tokio::test]
+async fn download_not_modified() {
+    let url = status_server("304 Not Modified").await;
+    let stream = sequenced_stream_with_content_length(url, Some(10));
+
+    // a 3xx response without a location is no redirect
+    let result = stream.download_bytes().await;
+    assert!(!matches!(result, Err(rustube::Error::TooManyRedirects(_))), "{:?}", result);
+}
+
+#[tokio::test]
+async fn thumbnail() {
+    use rustube::video_info::player_response::video_details::Thumbnail;
+
+    let (mut url, _) = sequenced_server(COUNTED_SEGMENTS).await;
+    let mut stream = sequenced_stream(url.clone());
+    assert_eq!(stream.thumbnail_url(), None);
+
+    let thumbnail = |width: u64, url: &str| Thumbnail { width, height: width * 9 / 16, url: url.to_owned() };
+    Arc::make_mut(&mut stream.video_details).thumbnails = vec![
+        thumbnail(1280, "//i.ytimg.com/vi/sequenced00/maxresdefault.jpg"),
+        thumbnail(120, "//i.ytimg.com/vi/sequenced00/default.jpg"),
+    ];
+    assert_eq!(
+        stream.thumbnail_url().unwrap().as_str(),
+        "https://i.ytimg.com/vi/sequenced00/maxresdefault.jpg"
+    );
+
+    url.query_pairs_mut().append_pair("sq", "0");
+    Arc::make_mut(&mut stream.video_details).thumbnails = vec![thumbnail(1280, url.as_str())];
+    assert_eq!(stream.download_thumbnail().await.unwrap(), COUNTED_SEGMENTS[0]);
+}
+
+#[tokio::test]
+async fn download_sequenced_with_window() {
+    use rustube::DownloadOptions;
+
+    for window in [0, 1, 2, 16] {
+        let (url, requests) = sequenced_server(COUNTED_SEGMENTS).await;
+        let stream = sequenced_stream(url);
+
+        let path = std::env::temp_dir().join(format!("rustube_sequenced_window_{}.mp4", window));
+        let options = DownloadOptions::new().to_path(&path).sequenced_window(window);
+        stream.download_with(options).await.unwrap();
+
+        // however many segments are requested concurrently, they are written in order
+        assert_eq!(tokio::fs::read(&path).await.unwrap(), COUNTED_SEGMENTS.concat());
+        tokio::fs::remove_file(&path).await.unwrap();
+
+        if window <= 1 {
+            // without concurrency, the segments are requested one after the other
+            let requests = requests.lock().unwrap().clone();
+            let mut sorted = requests.clone();
+            sorted.sort();
+            assert_eq!(requests, sorted);
+        }
+    }
+}
+
+/// A server, which responds to every request with `status` (i.e. `403 Forbidden`) and no body.
+async fn status_server(status: &'static str) -> Url {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = Url::parse(&format!("http://{}/videoplayback?id=1", listener.local_addr().unwrap())).unwrap();
+
+    tokio::spawn(async move {
+        loop {
+            let (mut socket, _) = listener.accept().await.unwrap();
+
+            let mut buf = Vec::new();
+            while !buf.ends_with(b"\r\n\r\n") {
+                let mut byte = [0];
+                if socket.read(&mut byte).await.unwrap() == 0 { break; }
+                buf.push(byte[0]);
+            }
+
+            let response = format!("HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n", status);
+            socket.write_all(response.as_bytes()).await.unwrap();
+            socket.shutdown().await.unwrap();
+        }
+    });
+
+    url
+}
+
+/// Responds to every request with partial content, that consists of `body`.
+async fn partial_content_server(body: &'static [u8]) -> Url {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = Url::parse(&format!("http://{}/videoplayback?id=1", listener.local_addr().unwrap())).unwrap();
+
+    tokio::spawn(async move {
+        loop {
+            let (mut socket, _) = listener.accept().await.unwrap();
+
+            let mut buf = Vec::new();
+            while !buf.ends_with(b"\r\n\r\n") {
+                let mut byte = [0];
+                if socket.read(&mut byte).await.unwrap() == 0 { break; }
+                buf.push(byte[0]);
+            }
+
+            let response = [
+                format!(
+                    "HTTP/1.1 206 Partial Content\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    body.len()
+                ).as_bytes(),
+                body
+            ].concat();
+            socket.write_all(&response).await.unwrap();
+            socket.shutdown().await.unwrap();
+        }
+    });
+
+    url
+}
+
+#[tokio::test]
+async fn download_range_via_part_file() {
+    let path = std::env::temp_dir().join("rustube_range_part_file.mp4");
+
+    let stream = sequenced_stream_with_content_length(partial_content_server(b"0123456789").await, Some(100));
+    assert_eq!(stream.download_range_to(&path, 10..20).await.unwrap(), 10);
+    assert_eq!(tokio::fs::read(&path).await.unwrap(), b"0123456789");
+    tokio::fs::remove_file(&path).await.unwrap();
+
+    // a truncated range neither ends up at the path, nor leaves a part file behind
+    let stream = sequenced_stream_with_content_length(partial_content_server(b"0123").await, Some(100));
+    let err = stream.download_range_to(&path, 10..20).await.unwrap_err();
+    assert!(matches!(err, rustube::Error::Incomplete { expected: 10, actual: 4 }));
+    assert!(tokio::fs::metadata(&path).await.is_err());
+    let part_path = std::env::temp_dir().join(format!("rustube_range_part_file.mp4.{}.part", std::process::id()));
+    assert!(tokio::fs::metadata(&part_path).await.is_err());
+}
+
+#[tokio::test]
+async fn download_pausable_via_part_file() {
+    let path = std::env::temp_dir().join("rustube_pausable_part_file.mp4");
+
+    let stream = sequenced_stream_with_content_length(partial_content_server(b"0123456789").await, Some(10));
+    assert_eq!(stream.download_to_pausable(&path, rustube::PauseHandle::new()).await.unwrap(), 10);
+    assert_eq!(tokio::fs::read(&path).await.unwrap(), b"0123456789");
+    tokio::fs::remove_file(&path).await.unwrap();
+
+    // a truncated download is detected, and doesn't end up at the path
+    let stream = sequenced_stream_with_content_length(partial_content_server(b"0123").await, Some(10));
+    let err = stream.download_to_pausable(&path, rustube::PauseHandle::new()).await.unwrap_err();
+    assert!(matches!(err, rustube::Error::Incomplete { expected: 10, actual: 4 }));
+    assert!(tokio::fs::metadata(&path).await.is_err());
+}
+
+#[tokio::test]
+async fn download_if_modified_since() {
+    use rustube::DownloadOutcome;
+
+    let path = std::env::temp_dir().join("rustube_if_modified_since.mp4");
+    let since = chrono::Utc::now() - chrono::Duration::days(1);
+
+    let stream = sequenced_stream_with_content_length(status_server("304 Not Modified").await, Some(10));
+    let outcome = stream.download_to_if_modified_since(&path, since).await.unwrap();
+    assert!(matches!(outcome, DownloadOutcome::NotModified));
+    assert!(tokio::fs::metadata(&path).await.is_err());
+
+    let stream = sequenced_stream_with_content_length(partial_content_server(b"0123456789").await, Some(10));
+    let outcome = stream.download_to_if_modified_since(&path, since).await.unwrap();
+    assert!(matches!(outcome, DownloadOutcome::Downloaded(10)));
+    assert_eq!(tokio::fs::read(&path).await.unwrap(), b"0123456789");
+    tokio::fs::remove_file(&path).await.unwrap();
+}
+
+#[cfg(feature = "mux")]
+#[tokio::test]
+async fn mux_removes_tracks_on_error() {
+    let (url, _) = sequenced_server(COUNTED_SEGMENTS).await;
+    let video = sequenced_stream(url);
+    let mut audio = sequenced_stream_with_content_length(status_server("404 Not Found").await, Some(10));
+    audio.itag = 140;
+    audio.mime = "audio/mp4".parse().unwrap();
+
+    let dir = std::env::temp_dir().join("rustube_mux_error");
+    tokio::fs::create_dir_all(&dir).await.unwrap();
+    let result = video.mux_with_ffmpeg(&audio, dir.join("muxed.mp4"), "/nonexistent/ffmpeg").await;
+
+    assert!(result.is_err());
+    // neither the tracks, nor their part files are left behind
+    assert!(std::fs::read_dir(&dir).unwrap().next().is_none());
+    tokio::fs::remove_dir(&dir).await.unwrap();
+}
+
+#[tokio::test]
+async fn download_forbidden_deciphered_url() {
+    let url = status_server("403 Forbidden").await;
+    let ciphered_stream = |expire: i64| {
+        let mut url = url.clone();
+        url.query_pairs_mut().append_pair("expire", &expire.to_string());
+        let mut stream = sequenced_stream_with_content_length(url, Some(10));
+        stream.signature_cipher.s = Some("abc".to_owned());
+        stream
+    };
+
+    // a valid url, which is rejected, was deciphered incorrectly
+    let stream = ciphered_stream(chrono::Utc::now().timestamp() + 3600);
+    let err = stream.download_bytes().await.unwrap_err();
+    assert!(matches!(err, rustube::Error::Cipher(_)));
+
+    // an expired url is rejected regardless of the signature
+    let stream = ciphered_stream(chrono::Utc::now().timestamp() - 3600);
+    let err = stream.download_bytes().await.unwrap_err();
+    assert!(
+        matches!(err, rustube::Error::Request(ref e) if e.status() == Some(reqwest::StatusCode::FORBIDDEN)),
+        "{:?}", err
+    );
+}
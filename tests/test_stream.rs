@@ -0,0 +1,242 @@
+// The streams are built using `Stream::from_info`, which requires the `serde` feature.
+#![cfg(feature = "serde")]
+
+use std::sync::Arc;
+
+use rustube::{Id, Stream, StreamInfo, StreamKind, VideoDetails};
+use rustube::url::Url;
+use rustube::video_info::player_response::streaming_data::{AudioQuality, ProjectionType, Quality, SignatureCipher};
+
+/// The [`Stream`] every test starts from: a 360p, video-only mp4 [`Stream`].
+/// Tests adjust the properties they are interested in.
+fn stream() -> Stream {
+    let video_details = VideoDetails {
+        allow_ratings: false,
+        author: String::new(),
+        average_rating: 0.,
+        channel_id: String::new(),
+        is_crawlable: false,
+        is_live_content: false,
+        is_live_default_broadcast: false,
+        is_low_latency_live_stream: false,
+        is_owner_viewing: false,
+        is_private: false,
+        is_unplugged_corpus: false,
+        key_words: vec![],
+        length_seconds: 0,
+        short_description: String::new(),
+        thumbnails: vec![],
+        title: String::new(),
+        video_id: Id::from_str("properties0").unwrap(),
+        view_count: 0,
+    };
+
+    let info = StreamInfo {
+        mime: "video/mp4".parse().unwrap(),
+        codecs: vec!["avc1.4d401e".to_owned()],
+        is_progressive: false,
+        includes_video_track: true,
+        includes_audio_track: false,
+        format_type: None,
+        approx_duration_ms: None,
+        audio_channels: None,
+        audio_quality: None,
+        audio_sample_rate: None,
+        average_bitrate: None,
+        bitrate: None,
+        color_info: None,
+        content_length: None,
+        fps: 30,
+        height: Some(360),
+        high_replication: None,
+        index_range: None,
+        init_range: None,
+        is_otf: false,
+        itag: 0,
+        last_modified: chrono::Utc::now(),
+        loudness_db: None,
+        projection_type: ProjectionType::Rectangular,
+        quality: Quality::Medium,
+        quality_label: None,
+        signature_cipher: SignatureCipher {
+            url: Url::parse("http://localhost/videoplayback").unwrap(),
+            s: None,
+            sp: None,
+        },
+        width: None,
+    };
+
+    Stream::from_info(info, reqwest::Client::new(), Arc::new(video_details))
+}
+
+#[test]
+fn audio_summary() {
+    let mut stream = stream();
+    assert_eq!(stream.audio_info(), None);
+    assert_eq!(stream.audio_summary(), None);
+
+    stream.includes_audio_track = true;
+    assert_eq!(stream.audio_summary(), None);
+
+    stream.audio_quality = Some(AudioQuality::Medium);
+    stream.audio_sample_rate = Some(48000);
+    stream.audio_channels = Some(2);
+    assert_eq!(stream.audio_summary().as_deref(), Some("AUDIO_QUALITY_MEDIUM, 48kHz, stereo"));
+
+    stream.audio_sample_rate = Some(44100);
+    stream.audio_channels = Some(6);
+    assert_eq!(stream.audio_summary().as_deref(), Some("AUDIO_QUALITY_MEDIUM, 44.1kHz, 6 channels"));
+}
+
+#[test]
+fn signature_cipher_accessors() {
+    let url = Url::parse("https://example.com/videoplayback?id=1&signature=abc&itag=18").unwrap();
+    let cipher = SignatureCipher { url, s: Some("abc".to_owned()), sp: Some("signature".to_owned()) };
+
+    assert_eq!(cipher.signature_param(), "signature");
+    assert_eq!(cipher.signature(), Some("abc"));
+    assert_eq!(cipher.unsigned_url().as_str(), "https://example.com/videoplayback?id=1&itag=18");
+
+    let url = Url::parse("https://example.com/videoplayback?sig=abc").unwrap();
+    let cipher = SignatureCipher { url, s: None, sp: None };
+    assert_eq!(cipher.signature_param(), SignatureCipher::DEFAULT_SIGNATURE_PARAM);
+    assert_eq!(cipher.unsigned_url().as_str(), "https://example.com/videoplayback");
+}
+
+#[test]
+fn vr_streams() {
+    let flat = stream();
+    let mut equirectangular = stream();
+    equirectangular.projection_type = serde_json::from_str("\"EQUIRECTANGULAR\"").unwrap();
+    let mut mesh = stream();
+    mesh.projection_type = ProjectionType::Mesh;
+
+    assert!(!flat.is_360() && !flat.is_vr());
+    assert!(equirectangular.is_360() && equirectangular.is_vr());
+    assert!(!mesh.is_360() && mesh.is_vr());
+
+    let streams = [flat, equirectangular, mesh];
+    let non_vr: Vec<_> = Stream::filter_non_vr(&streams).collect();
+    assert_eq!(non_vr, vec![&streams[0]]);
+}
+
+#[test]
+fn auto_default() {
+    let stream = |height: u64, progressive: bool| {
+        let mut stream = stream();
+        stream.height = Some(height);
+        stream.includes_audio_track = progressive;
+        stream
+    };
+
+    let streams = [stream(1080, true), stream(360, true), stream(720, true), stream(720, false)];
+    assert_eq!(Stream::auto_default(&streams), Some(&streams[2]));
+
+    // all progressive streams are larger than 720p
+    let streams = [stream(1440, true), stream(1080, true), stream(480, false)];
+    assert_eq!(Stream::auto_default(&streams), Some(&streams[1]));
+
+    // without progressive streams, video-only ones are considered
+    let streams = [stream(1080, false), stream(480, false)];
+    assert_eq!(Stream::auto_default(&streams), Some(&streams[1]));
+
+    assert_eq!(Stream::auto_default(&[]), None);
+}
+
+#[test]
+fn stream_kind() {
+    let mut stream = stream();
+    assert_eq!(stream.kind(), StreamKind::VideoOnly);
+
+    stream.includes_audio_track = true;
+    assert_eq!(stream.kind(), StreamKind::Progressive);
+
+    stream.includes_video_track = false;
+    assert_eq!(stream.kind(), StreamKind::AudioOnly);
+
+    stream.includes_audio_track = false;
+    assert_eq!(stream.kind(), StreamKind::Unknown);
+}
+
+#[test]
+fn filter_streams() {
+    let stream = |itag: u64, video: bool, audio: bool| {
+        let mut stream = stream();
+        stream.itag = itag;
+        stream.includes_video_track = video;
+        stream.includes_audio_track = audio;
+        stream
+    };
+
+    let streams = [stream(1, true, true), stream(2, true, false), stream(3, false, true)];
+    let itags = |streams: &mut dyn Iterator<Item=&Stream>| streams.map(|stream| stream.itag).collect::<Vec<_>>();
+    assert_eq!(itags(&mut Stream::video_streams(&streams)), vec![1, 2]);
+    assert_eq!(itags(&mut Stream::audio_streams(&streams)), vec![1, 3]);
+    assert_eq!(itags(&mut Stream::progressive_streams(&streams)), vec![1]);
+    assert_eq!(itags(&mut Stream::filter_kind(&streams, StreamKind::AudioOnly)), vec![3]);
+}
+
+#[test]
+fn best_quality_agrees_with_quality_rank() {
+    let stream = |height: u64, bitrate: u64, quality: Quality| {
+        let mut stream = stream();
+        stream.height = Some(height);
+        stream.bitrate = Some(bitrate);
+        stream.quality = quality;
+        stream
+    };
+
+    // the quality label is ignored, only the resolution and then the bitrate count
+    let mut streams = vec![
+        stream(720, 2_000_000, Quality::Hd1080),
+        stream(1080, 1_000_000, Quality::Tiny),
+        stream(1080, 3_000_000, Quality::Tiny),
+        stream(480, 5_000_000, Quality::Hd2160),
+    ];
+    assert_eq!(Stream::best_quality(&streams, StreamKind::VideoOnly), Some(&streams[2]));
+    assert_eq!(Stream::worst_quality(&streams, StreamKind::VideoOnly), Some(&streams[3]));
+
+    streams.sort_by_key(Stream::quality_rank);
+    assert_eq!(Stream::best_quality(&streams, StreamKind::VideoOnly), streams.last());
+    assert_eq!(Stream::worst_quality(&streams, StreamKind::VideoOnly), streams.first());
+}
+
+#[test]
+fn file_extension() {
+    let stream = |mime: &str, codec: &str| {
+        let mut stream = stream();
+        stream.mime = mime.parse().unwrap();
+        stream.codecs = vec![codec.to_owned()];
+        stream
+    };
+
+    assert_eq!(stream("video/mp4", "avc1.4d401e").file_extension(), "mp4");
+    assert_eq!(stream("video/webm", "vp9").file_extension(), "webm");
+    assert_eq!(stream("audio/mp4", "mp4a.40.2").file_extension(), "m4a");
+    assert_eq!(stream("audio/webm", "opus").file_extension(), "opus");
+    assert_eq!(stream("audio/webm", "vorbis").file_extension(), "weba");
+}
+
+#[test]
+fn prefer_high_replication() {
+    let stream = |itag: u64, height: u64, high_replication: Option<bool>| {
+        let mut stream = stream();
+        stream.itag = itag;
+        stream.height = Some(height);
+        stream.high_replication = high_replication;
+        stream
+    };
+
+    let streams = [
+        stream(1, 720, None),
+        stream(2, 720, Some(true)),
+        stream(3, 480, Some(false)),
+        stream(4, 360, None),
+        stream(5, 360, Some(false)),
+    ];
+    let itags: Vec<_> = Stream::prefer_high_replication(&streams).map(|stream| stream.itag).collect();
+    // only the 720p stream has a high replication alternative
+    assert_eq!(itags, vec![2, 3, 4, 5]);
+    assert!(streams[1].is_high_replication());
+    assert!(!streams[2].is_high_replication());
+}